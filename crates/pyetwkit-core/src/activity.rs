@@ -0,0 +1,368 @@
+//! Activity-correlation tree reconstruction
+//!
+//! ETW events can carry an `activity_id` identifying a logical operation and
+//! a `related_activity_id` pointing at the activity that started it (e.g. a
+//! "transfer" event linking a request's activity to the worker activity that
+//! handles it). [`ActivityTracker`] accumulates events keyed by activity and
+//! reconstructs the resulting parent/child span tree.
+
+use crate::event::EtwEvent;
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use serde::Serialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Opcode marking the end of an activity, per the standard ETW Start(1)/
+/// Stop(2) convention (the same one `process.rs`'s `PROCESS_STOP_OPCODE`
+/// uses for the kernel Process provider) — an activity without a Stop event
+/// is still open, and its span is reported with an open-ended duration.
+const ACTIVITY_STOP_OPCODE: u8 = 2;
+
+/// Events grouped under a single activity ID
+#[derive(Debug, Clone)]
+struct ActivitySpan {
+    provider_id: Uuid,
+    parent_id: Option<Uuid>,
+    /// Earliest event timestamp seen for this activity, tracked as a
+    /// running minimum so out-of-order arrival doesn't move the start
+    start: DateTime<Utc>,
+    /// Timestamp of the latest Stop event (opcode 2) seen, if any. `None`
+    /// means the activity hasn't closed yet.
+    end: Option<DateTime<Utc>>,
+    events: Vec<EtwEvent>,
+}
+
+impl ActivitySpan {
+    fn new(event: &EtwEvent) -> Self {
+        Self {
+            provider_id: event.provider_id,
+            parent_id: None,
+            start: event.timestamp,
+            end: None,
+            events: Vec::new(),
+        }
+    }
+}
+
+/// A reconstructed node in the activity tree
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityNode {
+    pub activity_id: Uuid,
+    pub events: Vec<EtwEvent>,
+    pub children: Vec<ActivityNode>,
+}
+
+/// A single activity's span: start/end timestamps, duration, event count,
+/// and parent linkage, as returned by [`ActivityTracker::spans`]
+#[derive(Debug, Clone)]
+pub struct ActivitySpanInfo {
+    pub activity_id: Uuid,
+    pub provider_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub start: DateTime<Utc>,
+    /// `None` for an activity that hasn't seen a Stop event (opcode 2)
+    /// yet — reported open-ended rather than guessing an end time.
+    pub end: Option<DateTime<Utc>>,
+    /// `None` exactly when `end` is `None`
+    pub duration: Option<ChronoDuration>,
+    pub event_count: usize,
+}
+
+/// Reconstructs activity-correlation trees from a stream of events
+#[derive(Debug, Clone, Default)]
+pub struct ActivityTracker {
+    spans: HashMap<Uuid, ActivitySpan>,
+    /// Insertion order of activities, so roots() is deterministic
+    order: Vec<Uuid>,
+}
+
+impl ActivityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ingest an event, filing it under its `activity_id` and linking it to
+    /// `related_activity_id` as a parent, if present. Events without an
+    /// `activity_id` are ignored. Tracks the activity's start as a running
+    /// minimum over ingested timestamps and its end as the latest Stop
+    /// event (opcode 2) seen, so out-of-order arrival and activities that
+    /// never close are both handled correctly.
+    pub fn ingest(&mut self, event: &EtwEvent) {
+        let Some(activity_id) = event.activity_id else {
+            return;
+        };
+
+        if !self.spans.contains_key(&activity_id) {
+            self.order.push(activity_id);
+        }
+        let span = self
+            .spans
+            .entry(activity_id)
+            .or_insert_with(|| ActivitySpan::new(event));
+
+        span.start = span.start.min(event.timestamp);
+        if event.opcode == ACTIVITY_STOP_OPCODE {
+            span.end = Some(span.end.map_or(event.timestamp, |end| end.max(event.timestamp)));
+        }
+        if let Some(parent_id) = event.related_activity_id {
+            if span.parent_id.is_none() {
+                span.parent_id = Some(parent_id);
+            }
+        }
+        span.events.push(event.clone());
+    }
+
+    /// Activity IDs with no known parent (or whose parent hasn't been seen),
+    /// in the order they were first observed.
+    pub fn roots(&self) -> Vec<Uuid> {
+        self.order
+            .iter()
+            .filter(|id| match self.spans[id].parent_id {
+                Some(parent_id) => !self.spans.contains_key(&parent_id),
+                None => true,
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Reconstruct the full forest of activity trees
+    pub fn tree(&self) -> Vec<ActivityNode> {
+        let mut children_of: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for id in &self.order {
+            if let Some(parent_id) = self.spans[id].parent_id {
+                if self.spans.contains_key(&parent_id) {
+                    children_of.entry(parent_id).or_default().push(*id);
+                }
+            }
+        }
+
+        self.roots()
+            .into_iter()
+            .map(|id| self.build_node(id, &children_of))
+            .collect()
+    }
+
+    fn build_node(&self, activity_id: Uuid, children_of: &HashMap<Uuid, Vec<Uuid>>) -> ActivityNode {
+        let span = &self.spans[&activity_id];
+        let children = children_of
+            .get(&activity_id)
+            .into_iter()
+            .flatten()
+            .map(|&child_id| self.build_node(child_id, children_of))
+            .collect();
+
+        ActivityNode {
+            activity_id,
+            events: span.events.clone(),
+            children,
+        }
+    }
+
+    /// Look up all events recorded under a given activity ID
+    pub fn events_for(&self, activity_id: Uuid) -> &[EtwEvent] {
+        self.spans
+            .get(&activity_id)
+            .map(|span| span.events.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Each activity's start/end timestamps, duration, event count, and
+    /// parent activity, in first-seen order. An activity with no Stop
+    /// event yet has `end`/`duration` of `None` (open-ended).
+    pub fn spans(&self) -> Vec<ActivitySpanInfo> {
+        self.order
+            .iter()
+            .map(|id| {
+                let span = &self.spans[id];
+                ActivitySpanInfo {
+                    activity_id: *id,
+                    provider_id: span.provider_id,
+                    parent_id: span.parent_id,
+                    start: span.start,
+                    end: span.end,
+                    duration: span.end.map(|end| end - span.start),
+                    event_count: span.events.len(),
+                }
+            })
+            .collect()
+    }
+
+    /// Serialize the reconstructed activity forest (see [`Self::tree`]) to
+    /// JSON, for handing off to trace visualization tooling
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.tree())
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+}
+
+fn node_to_py(py: Python<'_>, node: &ActivityNode) -> PyResult<Py<PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("activity_id", node.activity_id.to_string())?;
+    dict.set_item(
+        "events",
+        node.events
+            .iter()
+            .cloned()
+            .map(crate::event::PyEtwEvent::from)
+            .collect::<Vec<_>>(),
+    )?;
+    let children = PyList::empty(py);
+    for child in &node.children {
+        children.append(node_to_py(py, child)?)?;
+    }
+    dict.set_item("children", children)?;
+    Ok(dict.into())
+}
+
+fn span_to_py(py: Python<'_>, span: &ActivitySpanInfo) -> PyResult<Py<PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("activity_id", span.activity_id.to_string())?;
+    dict.set_item("provider_id", span.provider_id.to_string())?;
+    dict.set_item("parent_id", span.parent_id.map(|id| id.to_string()))?;
+    dict.set_item("start", span.start.to_rfc3339())?;
+    dict.set_item("end", span.end.map(|end| end.to_rfc3339()))?;
+    dict.set_item(
+        "duration_secs",
+        span.duration.map(|d| d.num_milliseconds() as f64 / 1000.0),
+    )?;
+    dict.set_item("event_count", span.event_count)?;
+    Ok(dict.into())
+}
+
+/// Python wrapper for [`ActivityTracker`]
+#[pyclass(name = "ActivityTracker")]
+#[derive(Default)]
+pub struct PyActivityTracker {
+    inner: ActivityTracker,
+}
+
+#[pymethods]
+impl PyActivityTracker {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ingest an event for correlation
+    fn ingest(&mut self, event: &crate::event::PyEtwEvent) {
+        self.inner.ingest(event.inner());
+    }
+
+    /// Each activity's start/end timestamps (RFC 3339), duration (seconds,
+    /// `None` if still open), event count, and parent activity ID, as
+    /// dicts, in first-seen order
+    fn spans(&self, py: Python<'_>) -> PyResult<Vec<Py<PyDict>>> {
+        self.inner
+            .spans()
+            .iter()
+            .map(|span| span_to_py(py, span))
+            .collect()
+    }
+
+    /// Reconstructed forest of activity trees, as nested dicts with
+    /// `activity_id`, `events`, and `children` keys
+    fn tree(&self, py: Python<'_>) -> PyResult<Vec<Py<PyDict>>> {
+        self.inner.tree().iter().map(|node| node_to_py(py, node)).collect()
+    }
+
+    /// Root activity IDs (as strings), in first-seen order
+    fn roots(&self) -> Vec<String> {
+        self.inner.roots().iter().map(|id| id.to_string()).collect()
+    }
+
+    /// Forest of activity trees serialized to a JSON string, for trace
+    /// visualization tooling
+    fn to_json(&self) -> PyResult<String> {
+        self.inner
+            .to_json()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Events recorded under a given activity ID (as a GUID string)
+    fn events_for(&self, activity_id: &str) -> PyResult<Vec<crate::event::PyEtwEvent>> {
+        let id = Uuid::parse_str(activity_id)
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("Invalid GUID format"))?;
+        Ok(self
+            .inner
+            .events_for(id)
+            .iter()
+            .cloned()
+            .map(crate::event::PyEtwEvent::from)
+            .collect())
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ActivityTracker(activities={})", self.inner.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EtwEvent;
+
+    fn event_with_activity(activity_id: Uuid, related: Option<Uuid>) -> EtwEvent {
+        let mut event = EtwEvent::new(Uuid::new_v4(), 1);
+        event.activity_id = Some(activity_id);
+        event.related_activity_id = related;
+        event
+    }
+
+    #[test]
+    fn test_events_without_activity_id_are_ignored() {
+        let mut tracker = ActivityTracker::new();
+        tracker.ingest(&EtwEvent::new(Uuid::new_v4(), 1));
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn test_single_activity_is_its_own_root() {
+        let mut tracker = ActivityTracker::new();
+        let activity = Uuid::new_v4();
+        tracker.ingest(&event_with_activity(activity, None));
+
+        assert_eq!(tracker.roots(), vec![activity]);
+        assert_eq!(tracker.events_for(activity).len(), 1);
+    }
+
+    #[test]
+    fn test_parent_child_tree_reconstruction() {
+        let mut tracker = ActivityTracker::new();
+        let parent = Uuid::new_v4();
+        let child = Uuid::new_v4();
+
+        tracker.ingest(&event_with_activity(parent, None));
+        tracker.ingest(&event_with_activity(child, Some(parent)));
+
+        let tree = tracker.tree();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].activity_id, parent);
+        assert_eq!(tree[0].children.len(), 1);
+        assert_eq!(tree[0].children[0].activity_id, child);
+    }
+
+    #[test]
+    fn test_orphaned_child_becomes_root() {
+        let mut tracker = ActivityTracker::new();
+        let child = Uuid::new_v4();
+        let missing_parent = Uuid::new_v4();
+
+        tracker.ingest(&event_with_activity(child, Some(missing_parent)));
+
+        assert_eq!(tracker.roots(), vec![child]);
+    }
+}
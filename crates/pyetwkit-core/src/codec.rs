@@ -0,0 +1,509 @@
+//! Compact binary codec for `EtwEvent`/`EventValue`
+//!
+//! JSON is convenient but slow and bulky for large captures. This module
+//! provides a tagged binary format: each `EventValue` is a 1-byte type tag
+//! followed by its little-endian payload, with varint-prefixed lengths for
+//! strings/binary/arrays/structs. `EtwEvent::to_bytes`/`from_bytes` frame the
+//! fixed-width header fields the same way, then the property map.
+
+use crate::error::{EtwError, Result};
+use crate::event::{EtwEvent, EventValue};
+
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Reconstruct a `DateTime<Utc>` from Unix nanoseconds, falling back to the
+/// current time if the value is out of chrono's representable range.
+fn nanos_to_datetime(nanos: i64) -> DateTime<Utc> {
+    let secs = nanos.div_euclid(1_000_000_000);
+    let subsec_nanos = nanos.rem_euclid(1_000_000_000) as u32;
+    Utc.timestamp_opt(secs, subsec_nanos)
+        .single()
+        .unwrap_or_else(Utc::now)
+}
+
+// Type tags for `EventValue` variants.
+const TAG_NULL: u8 = 0;
+const TAG_BOOL: u8 = 1;
+const TAG_I8: u8 = 2;
+const TAG_U8: u8 = 3;
+const TAG_I16: u8 = 4;
+const TAG_U16: u8 = 5;
+const TAG_I32: u8 = 6;
+const TAG_U32: u8 = 7;
+const TAG_I64: u8 = 8;
+const TAG_U64: u8 = 9;
+const TAG_F32: u8 = 10;
+const TAG_F64: u8 = 11;
+const TAG_STRING: u8 = 12;
+const TAG_BINARY: u8 = 13;
+const TAG_GUID: u8 = 14;
+const TAG_POINTER: u8 = 15;
+const TAG_FILETIME: u8 = 16;
+const TAG_SYSTEMTIME: u8 = 17;
+const TAG_SID: u8 = 18;
+const TAG_ARRAY: u8 = 19;
+const TAG_STRUCT: u8 = 20;
+
+/// A cursor over a byte slice with bounds-checked reads, so malformed or
+/// truncated input yields a `DecodeError` instead of panicking.
+pub(crate) struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    pub(crate) fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| EtwError::DecodeError("unexpected end of input".into()))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn i64(&mut self) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn varint(&mut self) -> Result<u64> {
+        let mut result: u64 = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.u8()?;
+            result |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+            shift += 7;
+            if shift >= 64 {
+                return Err(EtwError::DecodeError("varint too large".into()));
+            }
+        }
+    }
+
+    pub(crate) fn bytes_with_varint_len(&mut self) -> Result<&'a [u8]> {
+        let len = self.varint()? as usize;
+        self.take(len)
+    }
+}
+
+pub(crate) fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+pub(crate) fn write_bytes_with_varint_len(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+fn encode_value(out: &mut Vec<u8>, value: &EventValue) {
+    match value {
+        EventValue::Null => out.push(TAG_NULL),
+        EventValue::Bool(b) => {
+            out.push(TAG_BOOL);
+            out.push(*b as u8);
+        }
+        EventValue::I8(n) => {
+            out.push(TAG_I8);
+            out.push(*n as u8);
+        }
+        EventValue::U8(n) => {
+            out.push(TAG_U8);
+            out.push(*n);
+        }
+        EventValue::I16(n) => {
+            out.push(TAG_I16);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        EventValue::U16(n) => {
+            out.push(TAG_U16);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        EventValue::I32(n) => {
+            out.push(TAG_I32);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        EventValue::U32(n) => {
+            out.push(TAG_U32);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        EventValue::I64(n) => {
+            out.push(TAG_I64);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        EventValue::U64(n) => {
+            out.push(TAG_U64);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        EventValue::F32(n) => {
+            out.push(TAG_F32);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        EventValue::F64(n) => {
+            out.push(TAG_F64);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        EventValue::String(s) => {
+            out.push(TAG_STRING);
+            write_bytes_with_varint_len(out, s.as_bytes());
+        }
+        EventValue::Binary(b) => {
+            out.push(TAG_BINARY);
+            write_bytes_with_varint_len(out, b);
+        }
+        EventValue::Guid(g) => {
+            out.push(TAG_GUID);
+            out.extend_from_slice(g.as_bytes());
+        }
+        EventValue::Pointer(p) => {
+            out.push(TAG_POINTER);
+            out.extend_from_slice(&p.to_le_bytes());
+        }
+        EventValue::FileTime(ft) => {
+            out.push(TAG_FILETIME);
+            out.extend_from_slice(&ft.to_le_bytes());
+        }
+        EventValue::SystemTime(st) => {
+            out.push(TAG_SYSTEMTIME);
+            out.extend_from_slice(&st.timestamp_nanos_opt().unwrap_or(0).to_le_bytes());
+        }
+        EventValue::Sid(s) => {
+            out.push(TAG_SID);
+            write_bytes_with_varint_len(out, s.as_bytes());
+        }
+        EventValue::Array(items) => {
+            out.push(TAG_ARRAY);
+            write_varint(out, items.len() as u64);
+            for item in items {
+                encode_value(out, item);
+            }
+        }
+        EventValue::Struct(map) => {
+            out.push(TAG_STRUCT);
+            write_varint(out, map.len() as u64);
+            for (k, v) in map {
+                write_bytes_with_varint_len(out, k.as_bytes());
+                encode_value(out, v);
+            }
+        }
+    }
+}
+
+fn decode_value(cur: &mut Cursor) -> Result<EventValue> {
+    let tag = cur.u8()?;
+    Ok(match tag {
+        TAG_NULL => EventValue::Null,
+        TAG_BOOL => EventValue::Bool(cur.u8()? != 0),
+        TAG_I8 => EventValue::I8(cur.u8()? as i8),
+        TAG_U8 => EventValue::U8(cur.u8()?),
+        TAG_I16 => EventValue::I16(cur.u16()? as i16),
+        TAG_U16 => EventValue::U16(cur.u16()?),
+        TAG_I32 => EventValue::I32(cur.u32()? as i32),
+        TAG_U32 => EventValue::U32(cur.u32()?),
+        TAG_I64 => EventValue::I64(cur.i64()?),
+        TAG_U64 => EventValue::U64(cur.u64()?),
+        TAG_F32 => EventValue::F32(f32::from_le_bytes(cur.take(4)?.try_into().unwrap())),
+        TAG_F64 => EventValue::F64(f64::from_le_bytes(cur.take(8)?.try_into().unwrap())),
+        TAG_STRING => EventValue::String(
+            String::from_utf8(cur.bytes_with_varint_len()?.to_vec())
+                .map_err(|e| EtwError::DecodeError(e.to_string()))?,
+        ),
+        TAG_BINARY => EventValue::Binary(cur.bytes_with_varint_len()?.to_vec()),
+        TAG_GUID => EventValue::Guid(Uuid::from_bytes(cur.take(16)?.try_into().unwrap())),
+        TAG_POINTER => EventValue::Pointer(cur.u64()?),
+        TAG_FILETIME => EventValue::FileTime(cur.i64()?),
+        TAG_SYSTEMTIME => EventValue::SystemTime(nanos_to_datetime(cur.i64()?)),
+        TAG_SID => EventValue::Sid(
+            String::from_utf8(cur.bytes_with_varint_len()?.to_vec())
+                .map_err(|e| EtwError::DecodeError(e.to_string()))?,
+        ),
+        TAG_ARRAY => {
+            let len = cur.varint()? as usize;
+            let mut items = Vec::with_capacity(len.min(4096));
+            for _ in 0..len {
+                items.push(decode_value(cur)?);
+            }
+            EventValue::Array(items)
+        }
+        TAG_STRUCT => {
+            let len = cur.varint()? as usize;
+            let mut map = HashMap::with_capacity(len.min(4096));
+            for _ in 0..len {
+                let key = String::from_utf8(cur.bytes_with_varint_len()?.to_vec())
+                    .map_err(|e| EtwError::DecodeError(e.to_string()))?;
+                map.insert(key, decode_value(cur)?);
+            }
+            EventValue::Struct(map)
+        }
+        other => return Err(EtwError::DecodeError(format!("unknown value tag {other}"))),
+    })
+}
+
+/// Encode an `EtwEvent` into the compact binary format
+pub fn encode_event(event: &EtwEvent) -> Vec<u8> {
+    let mut out = Vec::new();
+
+    out.extend_from_slice(event.provider_id.as_bytes());
+    out.extend_from_slice(&event.event_id.to_le_bytes());
+    out.push(event.version);
+    out.push(event.opcode);
+    out.push(event.level);
+    out.extend_from_slice(&event.keywords.to_le_bytes());
+    out.extend_from_slice(&event.process_id.to_le_bytes());
+    out.extend_from_slice(&event.thread_id.to_le_bytes());
+    out.extend_from_slice(
+        &event
+            .timestamp
+            .timestamp_nanos_opt()
+            .unwrap_or(0)
+            .to_le_bytes(),
+    );
+
+    match event.activity_id {
+        Some(id) => {
+            out.push(1);
+            out.extend_from_slice(id.as_bytes());
+        }
+        None => out.push(0),
+    }
+    match event.related_activity_id {
+        Some(id) => {
+            out.push(1);
+            out.extend_from_slice(id.as_bytes());
+        }
+        None => out.push(0),
+    }
+
+    out.extend_from_slice(&event.task.to_le_bytes());
+    out.push(event.channel);
+
+    match &event.provider_name {
+        Some(name) => {
+            out.push(1);
+            write_bytes_with_varint_len(&mut out, name.as_bytes());
+        }
+        None => out.push(0),
+    }
+
+    write_varint(&mut out, event.properties.len() as u64);
+    for (key, value) in &event.properties {
+        write_bytes_with_varint_len(&mut out, key.as_bytes());
+        encode_value(&mut out, value);
+    }
+
+    match &event.raw_data {
+        Some(data) => {
+            out.push(1);
+            write_bytes_with_varint_len(&mut out, data);
+        }
+        None => out.push(0),
+    }
+
+    match &event.stack_trace {
+        Some(trace) => {
+            out.push(1);
+            write_varint(&mut out, trace.len() as u64);
+            for addr in trace {
+                out.extend_from_slice(&addr.to_le_bytes());
+            }
+        }
+        None => out.push(0),
+    }
+
+    out
+}
+
+/// Decode an `EtwEvent` previously produced by [`encode_event`]
+pub fn decode_event(bytes: &[u8]) -> Result<EtwEvent> {
+    let mut cur = Cursor::new(bytes);
+
+    let provider_id = Uuid::from_bytes(cur.take(16)?.try_into().unwrap());
+    let event_id = cur.u16()?;
+    let version = cur.u8()?;
+    let opcode = cur.u8()?;
+    let level = cur.u8()?;
+    let keywords = cur.u64()?;
+    let process_id = cur.u32()?;
+    let thread_id = cur.u32()?;
+    let timestamp = nanos_to_datetime(cur.i64()?);
+
+    let activity_id = if cur.u8()? != 0 {
+        Some(Uuid::from_bytes(cur.take(16)?.try_into().unwrap()))
+    } else {
+        None
+    };
+    let related_activity_id = if cur.u8()? != 0 {
+        Some(Uuid::from_bytes(cur.take(16)?.try_into().unwrap()))
+    } else {
+        None
+    };
+
+    let task = cur.u16()?;
+    let channel = cur.u8()?;
+
+    let provider_name = if cur.u8()? != 0 {
+        Some(
+            String::from_utf8(cur.bytes_with_varint_len()?.to_vec())
+                .map_err(|e| EtwError::DecodeError(e.to_string()))?,
+        )
+    } else {
+        None
+    };
+
+    let prop_count = cur.varint()? as usize;
+    let mut properties = HashMap::with_capacity(prop_count.min(4096));
+    for _ in 0..prop_count {
+        let key = String::from_utf8(cur.bytes_with_varint_len()?.to_vec())
+            .map_err(|e| EtwError::DecodeError(e.to_string()))?;
+        properties.insert(key, decode_value(&mut cur)?);
+    }
+
+    let raw_data = if cur.u8()? != 0 {
+        Some(cur.bytes_with_varint_len()?.to_vec())
+    } else {
+        None
+    };
+
+    let stack_trace = if cur.u8()? != 0 {
+        let len = cur.varint()? as usize;
+        let mut trace = Vec::with_capacity(len.min(4096));
+        for _ in 0..len {
+            trace.push(cur.u64()?);
+        }
+        Some(trace)
+    } else {
+        None
+    };
+
+    Ok(EtwEvent {
+        provider_id,
+        provider_name,
+        event_id,
+        version,
+        opcode,
+        level,
+        keywords,
+        process_id,
+        thread_id,
+        timestamp,
+        activity_id,
+        related_activity_id,
+        task,
+        channel,
+        properties,
+        raw_data,
+        stack_trace,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_event() -> EtwEvent {
+        let mut event = EtwEvent::new(Uuid::new_v4(), 7);
+        event.provider_name = Some("Test-Provider".to_string());
+        event.activity_id = Some(Uuid::new_v4());
+        event.properties.insert(
+            "Message".to_string(),
+            EventValue::String("hello".to_string()),
+        );
+        event
+            .properties
+            .insert("Count".to_string(), EventValue::U32(42));
+        event.properties.insert(
+            "Items".to_string(),
+            EventValue::Array(vec![EventValue::U8(1), EventValue::U8(2)]),
+        );
+        event.stack_trace = Some(vec![0x1000, 0x2000]);
+        event
+    }
+
+    #[test]
+    fn test_round_trip_scalar_values() {
+        for value in [
+            EventValue::Null,
+            EventValue::Bool(true),
+            EventValue::I8(-5),
+            EventValue::U8(5),
+            EventValue::I64(-1234),
+            EventValue::U64(1234),
+            EventValue::F64(3.25),
+            EventValue::String("hi".to_string()),
+            EventValue::Binary(vec![1, 2, 3]),
+            EventValue::Guid(Uuid::new_v4()),
+            EventValue::Pointer(0xdead_beef),
+            EventValue::FileTime(116444736000000000),
+            EventValue::Sid("S-1-5-18".to_string()),
+        ] {
+            let mut out = Vec::new();
+            encode_value(&mut out, &value);
+            let mut cur = Cursor::new(&out);
+            let decoded = decode_value(&mut cur).unwrap();
+            assert_eq!(format!("{decoded:?}"), format!("{value:?}"));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_event() {
+        let event = sample_event();
+        let bytes = encode_event(&event);
+        let decoded = decode_event(&bytes).unwrap();
+
+        assert_eq!(decoded.event_id, event.event_id);
+        assert_eq!(decoded.provider_name, event.provider_name);
+        assert_eq!(decoded.activity_id, event.activity_id);
+        assert_eq!(decoded.properties.len(), event.properties.len());
+        assert_eq!(decoded.stack_trace, event.stack_trace);
+    }
+
+    #[test]
+    fn test_truncated_input_is_decode_error() {
+        let event = sample_event();
+        let bytes = encode_event(&event);
+        let truncated = &bytes[..bytes.len() / 2];
+
+        let result = decode_event(truncated);
+        assert!(matches!(result, Err(EtwError::DecodeError(_))));
+    }
+
+    #[test]
+    fn test_unknown_tag_is_decode_error() {
+        let mut bytes = vec![0u8; 16];
+        bytes.push(255); // invalid tag after provider_id-sized header
+        let result = decode_event(&bytes);
+        assert!(result.is_err());
+    }
+}
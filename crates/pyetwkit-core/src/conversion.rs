@@ -0,0 +1,228 @@
+//! String-spec type coercion for `EventValue` properties
+//!
+//! Schema parsing sometimes degrades a property to a `String` (or to the
+//! wrong integer width). This module lets callers recover the intended type
+//! by name instead of hand-rolling the conversion: `Conversion::from_str`
+//! parses specs like `"int"` or `"timestamp_fmt:%Y-%m-%d"`, and
+//! `EventValue::convert` applies one to a value.
+
+use crate::event::EventValue;
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A requested coercion for a property value, parsed from a spec string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conversion {
+    /// Leave the value as-is (`"bytes"`/`"string"`)
+    AsIs,
+    /// Coerce to a 64-bit integer (`"int"`)
+    Int,
+    /// Coerce to a 64-bit float (`"float"`)
+    Float,
+    /// Coerce to a boolean (`"bool"`)
+    Bool,
+    /// Interpret the raw value (e.g. a `FileTime`) as a timestamp (`"timestamp"`)
+    Timestamp,
+    /// Parse a string value as a naive (UTC-assumed) timestamp using a strftime format
+    TimestampFmt(String),
+    /// Parse a string value as a timezone-aware timestamp using a strftime format
+    TimestampTzFmt(String),
+}
+
+/// Error returned by [`Conversion::from_str`] or [`EventValue::convert`]
+#[derive(Error, Debug)]
+pub enum ConversionError {
+    /// The spec string didn't match any known conversion
+    #[error("unknown conversion spec: {0}")]
+    UnknownSpec(String),
+    /// The value's runtime type can't be converted this way
+    #[error("cannot apply conversion to this value")]
+    Unsupported,
+    /// The value's content failed to parse into the target type
+    #[error("failed to parse value: {0}")]
+    ParseFailed(String),
+}
+
+impl FromStr for Conversion {
+    type Err = ConversionError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        if let Some(fmt) = spec.strip_prefix("timestamp_fmt:") {
+            return Ok(Conversion::TimestampFmt(fmt.to_string()));
+        }
+        if let Some(fmt) = spec.strip_prefix("timestamp_tz_fmt:") {
+            return Ok(Conversion::TimestampTzFmt(fmt.to_string()));
+        }
+        match spec {
+            "bytes" | "string" => Ok(Conversion::AsIs),
+            "int" => Ok(Conversion::Int),
+            "float" => Ok(Conversion::Float),
+            "bool" => Ok(Conversion::Bool),
+            "timestamp" => Ok(Conversion::Timestamp),
+            other => Err(ConversionError::UnknownSpec(other.to_string())),
+        }
+    }
+}
+
+impl EventValue {
+    /// Apply a [`Conversion`] to this value, returning the coerced result
+    pub fn convert(&self, conv: &Conversion) -> Result<EventValue, ConversionError> {
+        match conv {
+            Conversion::AsIs => Ok(self.clone()),
+            Conversion::Int => self.as_i64().map(EventValue::I64),
+            Conversion::Float => self.as_f64().map(EventValue::F64),
+            Conversion::Bool => self.as_bool().map(EventValue::Bool),
+            Conversion::Timestamp => self.as_datetime().map(EventValue::SystemTime),
+            Conversion::TimestampFmt(fmt) => {
+                let s = self.as_str_for_parsing()?;
+                let naive = NaiveDateTime::parse_from_str(s, fmt)
+                    .map_err(|e| ConversionError::ParseFailed(e.to_string()))?;
+                Ok(EventValue::SystemTime(Utc.from_utc_datetime(&naive)))
+            }
+            Conversion::TimestampTzFmt(fmt) => {
+                let s = self.as_str_for_parsing()?;
+                let parsed = DateTime::parse_from_str(s, fmt)
+                    .map_err(|e| ConversionError::ParseFailed(e.to_string()))?;
+                Ok(EventValue::SystemTime(parsed.with_timezone(&Utc)))
+            }
+        }
+    }
+
+    fn as_str_for_parsing(&self) -> Result<&str, ConversionError> {
+        match self {
+            EventValue::String(s) => Ok(s.as_str()),
+            _ => Err(ConversionError::Unsupported),
+        }
+    }
+
+    fn as_i64(&self) -> Result<i64, ConversionError> {
+        match self {
+            EventValue::I8(n) => Ok(*n as i64),
+            EventValue::U8(n) => Ok(*n as i64),
+            EventValue::I16(n) => Ok(*n as i64),
+            EventValue::U16(n) => Ok(*n as i64),
+            EventValue::I32(n) => Ok(*n as i64),
+            EventValue::U32(n) => Ok(*n as i64),
+            EventValue::I64(n) => Ok(*n),
+            EventValue::U64(n) => Ok(*n as i64),
+            EventValue::Pointer(p) => Ok(*p as i64),
+            EventValue::FileTime(ft) => Ok(*ft),
+            EventValue::Bool(b) => Ok(*b as i64),
+            EventValue::String(s) => s
+                .parse()
+                .map_err(|_| ConversionError::ParseFailed(format!("not an integer: {s}"))),
+            _ => Err(ConversionError::Unsupported),
+        }
+    }
+
+    fn as_f64(&self) -> Result<f64, ConversionError> {
+        match self {
+            EventValue::F32(n) => Ok(*n as f64),
+            EventValue::F64(n) => Ok(*n),
+            EventValue::String(s) => s
+                .parse()
+                .map_err(|_| ConversionError::ParseFailed(format!("not a float: {s}"))),
+            _ => self.as_i64().map(|n| n as f64),
+        }
+    }
+
+    fn as_bool(&self) -> Result<bool, ConversionError> {
+        match self {
+            EventValue::Bool(b) => Ok(*b),
+            EventValue::String(s) => match s.to_lowercase().as_str() {
+                "true" | "1" | "yes" => Ok(true),
+                "false" | "0" | "no" => Ok(false),
+                other => Err(ConversionError::ParseFailed(format!(
+                    "not a boolean: {other}"
+                ))),
+            },
+            _ => self.as_i64().map(|n| n != 0),
+        }
+    }
+
+    /// Interpret this value as a point in time, using the same FileTime
+    /// (100ns-since-1601) convention the rest of the crate uses.
+    fn as_datetime(&self) -> Result<DateTime<Utc>, ConversionError> {
+        match self {
+            EventValue::SystemTime(dt) => Ok(*dt),
+            EventValue::FileTime(ft) => {
+                let unix_100ns = ft - 116_444_736_000_000_000i64;
+                let secs = unix_100ns / 10_000_000;
+                let nanos = ((unix_100ns % 10_000_000) * 100) as u32;
+                Utc.timestamp_opt(secs, nanos)
+                    .single()
+                    .ok_or_else(|| ConversionError::ParseFailed("out-of-range FileTime".into()))
+            }
+            EventValue::I64(secs) => Utc
+                .timestamp_opt(*secs, 0)
+                .single()
+                .ok_or_else(|| ConversionError::ParseFailed("out-of-range timestamp".into())),
+            EventValue::U64(secs) => Utc
+                .timestamp_opt(*secs as i64, 0)
+                .single()
+                .ok_or_else(|| ConversionError::ParseFailed("out-of-range timestamp".into())),
+            _ => Err(ConversionError::Unsupported),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversion_from_str() {
+        assert_eq!(Conversion::from_str("int").unwrap(), Conversion::Int);
+        assert_eq!(Conversion::from_str("bytes").unwrap(), Conversion::AsIs);
+        assert_eq!(
+            Conversion::from_str("timestamp_fmt:%Y-%m-%d").unwrap(),
+            Conversion::TimestampFmt("%Y-%m-%d".to_string())
+        );
+        assert!(Conversion::from_str("nonsense").is_err());
+    }
+
+    #[test]
+    fn test_convert_string_to_int() {
+        let value = EventValue::String("42".to_string());
+        let converted = value.convert(&Conversion::Int).unwrap();
+        assert!(matches!(converted, EventValue::I64(42)));
+    }
+
+    #[test]
+    fn test_convert_string_to_float() {
+        let value = EventValue::String("3.5".to_string());
+        let converted = value.convert(&Conversion::Float).unwrap();
+        assert!(matches!(converted, EventValue::F64(f) if (f - 3.5).abs() < f64::EPSILON));
+    }
+
+    #[test]
+    fn test_convert_string_to_bool() {
+        let value = EventValue::String("true".to_string());
+        assert!(matches!(
+            value.convert(&Conversion::Bool).unwrap(),
+            EventValue::Bool(true)
+        ));
+    }
+
+    #[test]
+    fn test_convert_timestamp_fmt() {
+        let value = EventValue::String("2024-01-02 03:04:05".to_string());
+        let conv = Conversion::TimestampFmt("%Y-%m-%d %H:%M:%S".to_string());
+        let converted = value.convert(&conv).unwrap();
+        match converted {
+            EventValue::SystemTime(dt) => assert_eq!(dt.to_rfc3339(), "2024-01-02T03:04:05+00:00"),
+            other => panic!("unexpected value: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_convert_unsupported_type_errors() {
+        let value = EventValue::Binary(vec![1, 2, 3]);
+        assert!(matches!(
+            value.convert(&Conversion::Int),
+            Err(ConversionError::Unsupported)
+        ));
+    }
+}
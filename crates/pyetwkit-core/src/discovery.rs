@@ -4,16 +4,24 @@
 //! installed on the system using Windows TDH APIs.
 
 use crate::error::{EtwError, Result};
+use parking_lot::RwLock;
 use pyo3::prelude::*;
 use std::collections::HashMap;
 use std::ffi::OsString;
 use std::os::windows::ffi::OsStringExt;
+use std::sync::OnceLock;
 use uuid::Uuid;
 use windows::core::GUID;
 use windows::Win32::System::Diagnostics::Etw::{
-    TdhEnumerateProviders, PROVIDER_ENUMERATION_INFO, TRACE_PROVIDER_INFO,
+    TdhEnumerateProviderFieldInformation, TdhEnumerateProviders, EVENT_FIELD_TYPE,
+    PROVIDER_ENUMERATION_INFO, PROVIDER_FIELD_INFO, PROVIDER_FIELD_INFOARRAY, TRACE_PROVIDER_INFO,
 };
 
+/// `EVENT_FIELD_TYPE` selector for provider keyword names
+pub(crate) const EVENT_FIELD_KEYWORD: i32 = 0;
+/// `EVENT_FIELD_TYPE` selector for provider level names
+pub(crate) const EVENT_FIELD_LEVEL: i32 = 1;
+
 /// Information about an ETW provider
 #[derive(Debug, Clone)]
 pub struct ProviderInfo {
@@ -149,18 +157,96 @@ pub fn get_provider_info(name_or_guid: &str) -> Result<Option<ProviderDetails>>
     match provider {
         Some(info) => {
             // Get keywords for this provider
-            let keywords = get_provider_keywords(&info.guid).unwrap_or_default();
+            let keywords = provider_fields(&info.guid, EVENT_FIELD_KEYWORD).unwrap_or_default();
             Ok(Some(ProviderDetails { info, keywords }))
         }
         None => Ok(None),
     }
 }
 
-/// Get keywords for a specific provider
-fn get_provider_keywords(_guid: &Uuid) -> Result<HashMap<String, u64>> {
-    // TdhGetProviderFieldInformation can be used to get keywords
-    // For now, return empty - this is a placeholder for full implementation
-    Ok(HashMap::new())
+/// Per-`(provider_id, field_type)` cache of TDH field name -> value maps, so
+/// repeated `resolve_keywords`/`resolve_level` calls don't re-enumerate.
+fn field_cache() -> &'static RwLock<HashMap<(Uuid, i32), HashMap<String, u64>>> {
+    static CACHE: OnceLock<RwLock<HashMap<(Uuid, i32), HashMap<String, u64>>>> = OnceLock::new();
+    CACHE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Get the name -> value map for a provider's keyword or level fields
+/// (selected by `field_type`, one of the `EVENT_FIELD_*` constants), caching
+/// the result per `(guid, field_type)`.
+pub(crate) fn provider_fields(guid: &Uuid, field_type: i32) -> Result<HashMap<String, u64>> {
+    let key = (*guid, field_type);
+    if let Some(cached) = field_cache().read().get(&key) {
+        return Ok(cached.clone());
+    }
+
+    let fields = enumerate_provider_fields(guid, field_type)?;
+    field_cache().write().insert(key, fields.clone());
+    Ok(fields)
+}
+
+/// Enumerate a provider's keyword or level names via
+/// `TdhEnumerateProviderFieldInformation`, building a case-insensitive
+/// name -> value map (the name is lowercased; the value is the keyword mask
+/// or numeric level as reported by TDH).
+fn enumerate_provider_fields(guid: &Uuid, field_type: i32) -> Result<HashMap<String, u64>> {
+    const ERROR_INSUFFICIENT_BUFFER: u32 = 122;
+    const ERROR_NOT_FOUND: u32 = 1168;
+
+    let guid_raw = uuid_to_guid(guid);
+    let field_type = EVENT_FIELD_TYPE(field_type);
+
+    unsafe {
+        let mut buffer_size: u32 = 0;
+        let probe =
+            TdhEnumerateProviderFieldInformation(&guid_raw, field_type, None, &mut buffer_size);
+        if probe == ERROR_NOT_FOUND {
+            // The provider has no fields of this kind (e.g. no keywords).
+            return Ok(HashMap::new());
+        }
+        if probe != 0 && probe != ERROR_INSUFFICIENT_BUFFER {
+            return Err(EtwError::TdhError(format!(
+                "TdhEnumerateProviderFieldInformation failed: {probe}"
+            )));
+        }
+        if buffer_size == 0 {
+            return Ok(HashMap::new());
+        }
+
+        let mut buffer: Vec<u8> = vec![0u8; buffer_size as usize];
+        let array_ptr = buffer.as_mut_ptr() as *mut PROVIDER_FIELD_INFOARRAY;
+        let result = TdhEnumerateProviderFieldInformation(
+            &guid_raw,
+            field_type,
+            Some(array_ptr),
+            &mut buffer_size,
+        );
+        if result == ERROR_NOT_FOUND {
+            return Ok(HashMap::new());
+        }
+        if result != 0 {
+            return Err(EtwError::TdhError(format!(
+                "TdhEnumerateProviderFieldInformation failed: {result}"
+            )));
+        }
+
+        let array = &*array_ptr;
+        let count = array.NumberOfElements as usize;
+        let base = array_ptr as *const u8;
+        let entries_offset = std::mem::offset_of!(PROVIDER_FIELD_INFOARRAY, FieldInfoArray);
+        let entry_size = std::mem::size_of::<PROVIDER_FIELD_INFO>();
+        let entries_base = base.add(entries_offset);
+
+        let mut fields = HashMap::with_capacity(count);
+        for i in 0..count {
+            let entry = &*(entries_base.add(i * entry_size) as *const PROVIDER_FIELD_INFO);
+            let name_ptr = base.add(entry.NameOffset as usize) as *const u16;
+            let name = read_wide_string(name_ptr);
+            fields.insert(name.to_lowercase(), entry.Value);
+        }
+
+        Ok(fields)
+    }
 }
 
 /// Convert Windows GUID to uuid::Uuid
@@ -168,6 +254,17 @@ fn guid_to_uuid(guid: GUID) -> Uuid {
     Uuid::from_fields(guid.data1, guid.data2, guid.data3, &guid.data4)
 }
 
+/// Convert uuid::Uuid to a Windows GUID
+fn uuid_to_guid(uuid: &Uuid) -> GUID {
+    let (data1, data2, data3, data4) = uuid.as_fields();
+    GUID {
+        data1,
+        data2,
+        data3,
+        data4: *data4,
+    }
+}
+
 /// Read a null-terminated wide string from a pointer
 unsafe fn read_wide_string(ptr: *const u16) -> String {
     if ptr.is_null() {
@@ -312,4 +409,11 @@ mod tests {
         // Should find at least one kernel provider
         assert!(!results.is_empty());
     }
+
+    #[test]
+    fn test_uuid_guid_round_trip() {
+        let uuid = Uuid::new_v4();
+        let guid = uuid_to_guid(&uuid);
+        assert_eq!(guid_to_uuid(guid), uuid);
+    }
 }
@@ -93,15 +93,24 @@ pub enum EtwError {
     /// Invalid file format
     #[error("Invalid file format: {0}")]
     InvalidFileFormat(String),
+
+    /// Failed to decode a binary-encoded value (truncated or corrupt input)
+    #[error("Failed to decode binary data: {0}")]
+    DecodeError(String),
+
+    /// A requested keyword/level name isn't in the provider's manifest
+    #[error("Keyword or level '{0}' not found in provider manifest")]
+    KeywordNotFound(String),
 }
 
 impl From<EtwError> for PyErr {
     fn from(err: EtwError) -> PyErr {
         match &err {
             EtwError::PermissionDenied => PyOSError::new_err(err.to_string()),
-            EtwError::InvalidProviderGuid(_) | EtwError::InvalidConfig(_) => {
-                PyValueError::new_err(err.to_string())
-            }
+            EtwError::InvalidProviderGuid(_)
+            | EtwError::InvalidConfig(_)
+            | EtwError::DecodeError(_)
+            | EtwError::KeywordNotFound(_) => PyValueError::new_err(err.to_string()),
             EtwError::WindowsError(_, code) => {
                 PyOSError::new_err(format!("{} (error code: {})", err, code))
             }
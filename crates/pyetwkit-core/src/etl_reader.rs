@@ -5,20 +5,68 @@
 
 use crate::error::{EtwError, Result};
 use crate::event::EtwEvent;
+use crate::filter::FilterBuilder;
+use crate::process::SharedProcessResolver;
 use crate::session::parse_event_record;
 
+use crossbeam_channel::unbounded;
 use ferrisetw::schema_locator::SchemaLocator;
 use ferrisetw::trace::FileTrace;
 use ferrisetw::EventRecord;
 use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{channel, Receiver};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
 use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How often [`EtlReader::select`] re-checks each reader's channel while
+/// waiting for the first one to become ready
+const SELECT_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Matched/dropped counts from filter pushdown during an ETL read
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct EtlReaderStats {
+    /// Events that passed the filter (or all events, if none was set) and were sent downstream
+    pub matched: u64,
+    /// Events discarded by the filter before ever crossing the channel
+    pub dropped: u64,
+}
+
+/// Lock-free matched/dropped counters, shared between the reader and its processing thread
+#[derive(Debug, Default)]
+struct EtlReaderCounters {
+    matched: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl EtlReaderCounters {
+    fn snapshot(&self) -> EtlReaderStats {
+        EtlReaderStats {
+            matched: self.matched.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Shared counters handle
+type SharedEtlReaderCounters = Arc<EtlReaderCounters>;
 
 /// ETL file reader for reading events from trace log files
 pub struct EtlReader {
     /// Path to the ETL file
     path: String,
+    /// Filter evaluated in the processing thread so unmatched events never cross the channel
+    filter: Option<FilterBuilder>,
+    /// Resolves each event's PID to a process name for `ProcessName` filters
+    /// and feeds process start/stop events back into the map as they're seen
+    resolver: Option<SharedProcessResolver>,
+    /// Matched/dropped counters from filter pushdown
+    counters: SharedEtlReaderCounters,
     /// Event receiver
     receiver: Option<Receiver<EtwEvent>>,
     /// Processing thread handle
@@ -37,23 +85,64 @@ impl EtlReader {
 
         Ok(Self {
             path: path_str,
+            filter: None,
+            resolver: None,
+            counters: Arc::new(EtlReaderCounters::default()),
             receiver: None,
             thread_handle: None,
         })
     }
 
+    /// Attach a filter, evaluated against each decoded event before it crosses the
+    /// channel; only matching events reach the reader
+    pub fn with_filter(mut self, filter: FilterBuilder) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Attach a process resolver, so `ProcessName`/`ProcessId` filters can
+    /// match events that don't themselves carry a decoded process name
+    pub fn with_process_resolver(mut self, resolver: SharedProcessResolver) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    /// Matched/dropped counts from filter pushdown so far
+    pub fn stats(&self) -> EtlReaderStats {
+        self.counters.snapshot()
+    }
+
     /// Start reading events from the file
     pub fn start(&mut self) -> Result<()> {
         let (tx, rx) = channel();
         self.receiver = Some(rx);
 
         let path = PathBuf::from(&self.path);
+        let filter = self.filter.clone();
+        let resolver = self.resolver.clone();
+        let counters = Arc::clone(&self.counters);
 
         // Spawn thread to process file
         let handle = thread::spawn(move || {
             let callback = move |record: &EventRecord, locator: &SchemaLocator| {
                 let schema = locator.event_schema(record).ok();
-                let event = parse_event_record(record, schema.as_ref().map(|s| s.as_ref()));
+                let event = parse_event_record(record, schema.as_ref().map(|s| s.as_ref()), None);
+
+                if let Some(resolver) = &resolver {
+                    resolver.record(&event);
+                }
+
+                if let Some(filter) = &filter {
+                    let matched = match &resolver {
+                        Some(resolver) => filter.matches_event_resolved(&event, resolver),
+                        None => filter.matches_event(&event),
+                    };
+                    if !matched {
+                        counters.dropped.fetch_add(1, Ordering::Relaxed);
+                        return;
+                    }
+                }
+                counters.matched.fetch_add(1, Ordering::Relaxed);
                 let _ = tx.send(event);
             };
 
@@ -79,6 +168,40 @@ impl EtlReader {
         self.receiver.as_ref()?.try_recv().ok()
     }
 
+    /// Wait up to `timeout` for the next event, starting the reader first if needed
+    pub fn poll(&mut self, timeout: Duration) -> Option<EtwEvent> {
+        if self.receiver.is_none() && self.start().is_err() {
+            return None;
+        }
+        self.receiver.as_ref()?.recv_timeout(timeout).ok()
+    }
+
+    /// Wait up to `timeout` for any of `readers` to have an event ready,
+    /// starting any that haven't begun reading yet. Returns the index of the
+    /// reader the event came from, or `None` if the timeout elapses first.
+    pub fn select(readers: &mut [EtlReader], timeout: Duration) -> Option<(usize, EtwEvent)> {
+        for reader in readers.iter_mut() {
+            if reader.receiver.is_none() {
+                let _ = reader.start();
+            }
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            for (index, reader) in readers.iter_mut().enumerate() {
+                if let Some(event) = reader.try_next_event() {
+                    return Some((index, event));
+                }
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return None;
+            }
+            thread::sleep(remaining.min(SELECT_POLL_INTERVAL));
+        }
+    }
+
     /// Check if reading is complete
     pub fn is_finished(&self) -> bool {
         if let Some(handle) = &self.thread_handle {
@@ -115,6 +238,235 @@ impl Iterator for EtlReader {
     }
 }
 
+/// One pending event in [`MultiEtlReader`]'s merge heap, ordered so the
+/// earliest timestamp (ties broken by file index) sorts first
+#[derive(Debug)]
+struct MergeEntry {
+    timestamp: chrono::DateTime<chrono::Utc>,
+    file_index: usize,
+    event: EtwEvent,
+}
+
+impl PartialEq for MergeEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.timestamp == other.timestamp && self.file_index == other.file_index
+    }
+}
+
+impl Eq for MergeEntry {}
+
+impl PartialOrd for MergeEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MergeEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.timestamp
+            .cmp(&other.timestamp)
+            .then_with(|| self.file_index.cmp(&other.file_index))
+    }
+}
+
+/// Reads several ETL files concurrently (bounded to `max_workers` files
+/// processed at once) and yields a single globally time-ordered stream,
+/// k-way merged on event timestamp
+pub struct MultiEtlReader {
+    /// Paths to the ETL files
+    paths: Vec<PathBuf>,
+    /// Maximum number of files processed at the same time
+    max_workers: usize,
+    /// Filter evaluated in each file's worker thread before merging
+    filter: Option<FilterBuilder>,
+    /// Resolves each event's PID to a process name for `ProcessName` filters
+    resolver: Option<SharedProcessResolver>,
+    /// Merged, time-ordered event receiver
+    receiver: Option<Receiver<EtwEvent>>,
+    /// Worker and merge thread handles
+    thread_handles: Vec<JoinHandle<()>>,
+}
+
+impl MultiEtlReader {
+    /// Create a reader over the given ETL files. Defaults to one worker per
+    /// file; use [`Self::with_max_workers`] to bound concurrency.
+    pub fn new<P: AsRef<Path>>(paths: Vec<P>) -> Result<Self> {
+        let paths: Vec<PathBuf> = paths.iter().map(|p| p.as_ref().to_path_buf()).collect();
+        for path in &paths {
+            if !path.exists() {
+                return Err(EtwError::FileNotFound(path.to_string_lossy().to_string()));
+            }
+        }
+
+        let max_workers = paths.len().max(1);
+        Ok(Self {
+            paths,
+            max_workers,
+            filter: None,
+            resolver: None,
+            receiver: None,
+            thread_handles: Vec::new(),
+        })
+    }
+
+    /// Bound how many files are processed concurrently
+    pub fn with_max_workers(mut self, max_workers: usize) -> Self {
+        self.max_workers = max_workers.max(1);
+        self
+    }
+
+    /// Attach a filter, evaluated in each file's worker thread before the
+    /// event ever reaches the merge step
+    pub fn with_filter(mut self, filter: FilterBuilder) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Attach a process resolver shared across every file's worker thread
+    pub fn with_process_resolver(mut self, resolver: SharedProcessResolver) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    /// Start processing every file and merging their events into a single
+    /// time-ordered stream
+    pub fn start(&mut self) -> Result<()> {
+        let mut per_file_senders = Vec::with_capacity(self.paths.len());
+        let mut per_file_receivers = Vec::with_capacity(self.paths.len());
+        for _ in &self.paths {
+            let (tx, rx) = channel();
+            per_file_senders.push(tx);
+            per_file_receivers.push(rx);
+        }
+
+        let (job_tx, job_rx) = unbounded::<usize>();
+        for index in 0..self.paths.len() {
+            let _ = job_tx.send(index);
+        }
+        drop(job_tx);
+
+        let paths = self.paths.clone();
+        let per_file_senders = Arc::new(per_file_senders);
+        let mut handles = Vec::with_capacity(self.max_workers + 1);
+        for _ in 0..self.max_workers {
+            let job_rx = job_rx.clone();
+            let paths = paths.clone();
+            let per_file_senders = Arc::clone(&per_file_senders);
+            let filter = self.filter.clone();
+            let resolver = self.resolver.clone();
+            handles.push(thread::spawn(move || {
+                while let Ok(file_index) = job_rx.recv() {
+                    let path = paths[file_index].clone();
+                    let tx = per_file_senders[file_index].clone();
+                    let filter = filter.clone();
+                    let resolver = resolver.clone();
+                    let callback = move |record: &EventRecord, locator: &SchemaLocator| {
+                        let schema = locator.event_schema(record).ok();
+                        let event = parse_event_record(record, schema.as_ref().map(|s| s.as_ref()), None);
+
+                        if let Some(resolver) = &resolver {
+                            resolver.record(&event);
+                        }
+
+                        if let Some(filter) = &filter {
+                            let matched = match &resolver {
+                                Some(resolver) => filter.matches_event_resolved(&event, resolver),
+                                None => filter.matches_event(&event),
+                            };
+                            if !matched {
+                                return;
+                            }
+                        }
+                        let _ = tx.send(event);
+                    };
+
+                    let trace_builder = FileTrace::new(path, callback);
+                    let _ = trace_builder.start_and_process();
+                }
+            }));
+        }
+
+        let (out_tx, out_rx) = channel();
+        handles.push(thread::spawn(move || {
+            Self::merge(per_file_receivers, out_tx);
+        }));
+
+        self.thread_handles = handles;
+        self.receiver = Some(out_rx);
+        Ok(())
+    }
+
+    /// K-way merge: seed a min-heap with one event per source, then
+    /// repeatedly pop the earliest and refill only the source it came from.
+    /// A source is dropped from the heap once its channel closes (EOF).
+    fn merge(receivers: Vec<Receiver<EtwEvent>>, out_tx: Sender<EtwEvent>) {
+        let mut heap = BinaryHeap::new();
+        for (file_index, rx) in receivers.iter().enumerate() {
+            if let Ok(event) = rx.recv() {
+                heap.push(Reverse(MergeEntry {
+                    timestamp: event.timestamp,
+                    file_index,
+                    event,
+                }));
+            }
+        }
+
+        while let Some(Reverse(entry)) = heap.pop() {
+            let file_index = entry.file_index;
+            if out_tx.send(entry.event).is_err() {
+                break;
+            }
+            if let Ok(next_event) = receivers[file_index].recv() {
+                heap.push(Reverse(MergeEntry {
+                    timestamp: next_event.timestamp,
+                    file_index,
+                    event: next_event,
+                }));
+            }
+        }
+    }
+
+    /// Get the next merged event
+    pub fn next_event(&mut self) -> Option<EtwEvent> {
+        self.receiver.as_ref()?.recv().ok()
+    }
+
+    /// Try to get the next merged event without blocking
+    pub fn try_next_event(&mut self) -> Option<EtwEvent> {
+        self.receiver.as_ref()?.try_recv().ok()
+    }
+
+    /// Check if every worker and the merge thread have finished
+    pub fn is_finished(&self) -> bool {
+        self.thread_handles.iter().all(|h| h.is_finished())
+    }
+
+    /// Wait for every worker and the merge thread to finish
+    pub fn wait(&mut self) {
+        for handle in self.thread_handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+
+    /// Paths to the ETL files being read
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+}
+
+impl Iterator for MultiEtlReader {
+    type Item = EtwEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.receiver.is_none() {
+            if self.start().is_err() {
+                return None;
+            }
+        }
+        self.next_event()
+    }
+}
+
 /// Python wrapper for EtlReader
 #[pyclass(name = "EtlReader")]
 pub struct PyEtlReader {
@@ -149,6 +501,60 @@ impl PyEtlReader {
         self.inner.as_ref().map(|r| r.is_finished()).unwrap_or(true)
     }
 
+    /// Attach a filter so only matching events are read from the file; call
+    /// before iterating or `read_all()`
+    fn with_filter(&mut self, filter: &crate::filter::PyEventFilter) -> PyResult<()> {
+        let reader = self
+            .inner
+            .take()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Reader is closed"))?;
+
+        let builder = filter
+            .filters
+            .iter()
+            .cloned()
+            .fold(FilterBuilder::new(), FilterBuilder::filter);
+        self.inner = Some(reader.with_filter(builder));
+        Ok(())
+    }
+
+    /// Attach a process resolver so `ProcessName`/`ProcessId` filters can
+    /// match events that don't carry a decoded process name themselves
+    fn with_process_resolver(
+        &mut self,
+        resolver: &crate::process::PyProcessResolver,
+    ) -> PyResult<()> {
+        let reader = self
+            .inner
+            .take()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Reader is closed"))?;
+
+        self.inner = Some(reader.with_process_resolver(resolver.shared()));
+        Ok(())
+    }
+
+    /// Wait up to `timeout_ms` milliseconds for the next event, without
+    /// spinning; returns `None` on timeout rather than blocking forever
+    fn poll(&mut self, timeout_ms: u64) -> PyResult<Option<crate::event::PyEtwEvent>> {
+        let reader = self
+            .inner
+            .as_mut()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Reader is closed"))?;
+        self.started = true;
+
+        Ok(reader
+            .poll(std::time::Duration::from_millis(timeout_ms))
+            .map(crate::event::PyEtwEvent::from))
+    }
+
+    /// Matched/dropped counts from filter pushdown so far
+    fn stats(&self) -> PyResult<PyEtlReaderStats> {
+        self.inner
+            .as_ref()
+            .map(|r| PyEtlReaderStats::from(r.stats()))
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Reader is closed"))
+    }
+
     /// Read all events as a list
     fn read_all(&mut self) -> PyResult<Vec<crate::event::PyEtwEvent>> {
         let reader = self
@@ -214,6 +620,182 @@ impl PyEtlReader {
     }
 }
 
+/// Python wrapper for EtlReaderStats
+#[pyclass(name = "EtlReaderStats")]
+#[derive(Clone)]
+pub struct PyEtlReaderStats {
+    inner: EtlReaderStats,
+}
+
+#[pymethods]
+impl PyEtlReaderStats {
+    /// Number of events that passed the filter and were sent downstream
+    #[getter]
+    fn matched(&self) -> u64 {
+        self.inner.matched
+    }
+
+    /// Number of events dropped by the filter before crossing the channel
+    #[getter]
+    fn dropped(&self) -> u64 {
+        self.inner.dropped
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "EtlReaderStats(matched={}, dropped={})",
+            self.inner.matched, self.inner.dropped
+        )
+    }
+}
+
+impl From<EtlReaderStats> for PyEtlReaderStats {
+    fn from(stats: EtlReaderStats) -> Self {
+        Self { inner: stats }
+    }
+}
+
+/// Python wrapper for MultiEtlReader
+#[pyclass(name = "MultiEtlReader")]
+pub struct PyMultiEtlReader {
+    inner: Option<MultiEtlReader>,
+    started: bool,
+}
+
+#[pymethods]
+impl PyMultiEtlReader {
+    /// Create a reader over several ETL files, merged into a single
+    /// time-ordered stream. `max_workers` bounds how many files are
+    /// processed concurrently (default: one worker per file).
+    #[new]
+    #[pyo3(signature = (paths, max_workers=None))]
+    fn new(paths: Vec<String>, max_workers: Option<usize>) -> PyResult<Self> {
+        let mut reader = MultiEtlReader::new(paths)
+            .map_err(|e| pyo3::exceptions::PyFileNotFoundError::new_err(e.to_string()))?;
+        if let Some(max_workers) = max_workers {
+            reader = reader.with_max_workers(max_workers);
+        }
+        Ok(Self {
+            inner: Some(reader),
+            started: false,
+        })
+    }
+
+    /// Paths to the ETL files being read
+    #[getter]
+    fn paths(&self) -> PyResult<Vec<String>> {
+        self.inner
+            .as_ref()
+            .map(|r| {
+                r.paths()
+                    .iter()
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .collect()
+            })
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Reader is closed"))
+    }
+
+    /// Check if every file has finished being read
+    fn is_finished(&self) -> bool {
+        self.inner.as_ref().map(|r| r.is_finished()).unwrap_or(true)
+    }
+
+    /// Attach a filter so only matching events are read from each file; call
+    /// before iterating or `read_all()`
+    fn with_filter(&mut self, filter: &crate::filter::PyEventFilter) -> PyResult<()> {
+        let reader = self
+            .inner
+            .take()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Reader is closed"))?;
+
+        let builder = filter
+            .filters
+            .iter()
+            .cloned()
+            .fold(FilterBuilder::new(), FilterBuilder::filter);
+        self.inner = Some(reader.with_filter(builder));
+        Ok(())
+    }
+
+    /// Attach a process resolver, shared across every file's worker thread
+    fn with_process_resolver(
+        &mut self,
+        resolver: &crate::process::PyProcessResolver,
+    ) -> PyResult<()> {
+        let reader = self
+            .inner
+            .take()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Reader is closed"))?;
+
+        self.inner = Some(reader.with_process_resolver(resolver.shared()));
+        Ok(())
+    }
+
+    /// Read all events, time-ordered across every file, as a list
+    fn read_all(&mut self) -> PyResult<Vec<crate::event::PyEtwEvent>> {
+        let reader = self
+            .inner
+            .as_mut()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Reader is closed"))?;
+
+        if !self.started {
+            reader
+                .start()
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+            self.started = true;
+        }
+
+        let mut events = Vec::new();
+        while let Some(event) = reader.next_event() {
+            events.push(crate::event::PyEtwEvent::from(event));
+        }
+        reader.wait();
+
+        Ok(events)
+    }
+
+    /// Context manager enter
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Context manager exit
+    #[pyo3(signature = (_exc_type=None, _exc_val=None, _exc_tb=None))]
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<PyObject>,
+        _exc_val: Option<PyObject>,
+        _exc_tb: Option<PyObject>,
+    ) -> bool {
+        if let Some(mut reader) = self.inner.take() {
+            reader.wait();
+        }
+        false
+    }
+
+    /// Iterator protocol
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Get next merged event
+    fn __next__(&mut self) -> PyResult<Option<crate::event::PyEtwEvent>> {
+        let reader = self
+            .inner
+            .as_mut()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Reader is closed"))?;
+
+        if !self.started {
+            reader
+                .start()
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+            self.started = true;
+        }
+
+        Ok(reader.try_next_event().map(crate::event::PyEtwEvent::from))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,4 +805,103 @@ mod tests {
         let result = EtlReader::new("nonexistent.etl");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_with_filter_stores_filter() {
+        let reader = EtlReader {
+            path: "dummy.etl".to_string(),
+            filter: None,
+            resolver: None,
+            counters: Arc::new(EtlReaderCounters::default()),
+            receiver: None,
+            thread_handle: None,
+        }
+        .with_filter(FilterBuilder::new().event_ids([1, 2, 3]));
+
+        assert!(reader.filter.is_some());
+        assert_eq!(reader.stats().matched, 0);
+        assert_eq!(reader.stats().dropped, 0);
+    }
+
+    fn reader_with_channel(path: &str) -> (EtlReader, std::sync::mpsc::Sender<EtwEvent>) {
+        let (tx, rx) = channel();
+        let reader = EtlReader {
+            path: path.to_string(),
+            filter: None,
+            resolver: None,
+            counters: Arc::new(EtlReaderCounters::default()),
+            receiver: Some(rx),
+            thread_handle: None,
+        };
+        (reader, tx)
+    }
+
+    #[test]
+    fn test_poll_times_out_without_event() {
+        let (mut reader, _tx) = reader_with_channel("dummy.etl");
+        assert!(reader.poll(Duration::from_millis(10)).is_none());
+    }
+
+    #[test]
+    fn test_select_returns_ready_reader() {
+        let (reader_a, _tx_a) = reader_with_channel("a.etl");
+        let (reader_b, tx_b) = reader_with_channel("b.etl");
+        tx_b.send(EtwEvent::new(uuid::Uuid::new_v4(), 42)).unwrap();
+
+        let mut readers = [reader_a, reader_b];
+        let (index, event) = EtlReader::select(&mut readers, Duration::from_millis(50)).unwrap();
+
+        assert_eq!(index, 1);
+        assert_eq!(event.event_id, 42);
+    }
+
+    fn event_at(event_id: u16, offset_secs: i64) -> EtwEvent {
+        let mut event = EtwEvent::new(uuid::Uuid::new_v4(), event_id);
+        event.timestamp = chrono::Utc::now() + chrono::Duration::seconds(offset_secs);
+        event
+    }
+
+    #[test]
+    fn test_multi_etl_reader_rejects_missing_file() {
+        let result = MultiEtlReader::new(vec!["nonexistent.etl"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_merge_orders_events_by_timestamp_across_sources() {
+        let (tx_a, rx_a) = channel();
+        let (tx_b, rx_b) = channel();
+
+        tx_a.send(event_at(1, 2)).unwrap();
+        tx_a.send(event_at(2, 4)).unwrap();
+        drop(tx_a);
+
+        tx_b.send(event_at(3, 1)).unwrap();
+        tx_b.send(event_at(4, 3)).unwrap();
+        drop(tx_b);
+
+        let (out_tx, out_rx) = channel();
+        MultiEtlReader::merge(vec![rx_a, rx_b], out_tx);
+
+        let merged: Vec<u16> = out_rx.iter().map(|e| e.event_id).collect();
+        assert_eq!(merged, vec![3, 1, 4, 2]);
+    }
+
+    #[test]
+    fn test_merge_drops_source_on_eof() {
+        let (tx_a, rx_a) = channel();
+        let (tx_b, rx_b) = channel();
+
+        // `rx_b` closes immediately with nothing sent; the merge must still
+        // drain `rx_a` instead of waiting on the closed source forever.
+        drop(tx_b);
+        tx_a.send(event_at(1, 0)).unwrap();
+        drop(tx_a);
+
+        let (out_tx, out_rx) = channel();
+        MultiEtlReader::merge(vec![rx_a, rx_b], out_tx);
+
+        let merged: Vec<u16> = out_rx.iter().map(|e| e.event_id).collect();
+        assert_eq!(merged, vec![1]);
+    }
 }
@@ -126,6 +126,16 @@ impl EtwEvent {
     pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
     }
+
+    /// Encode to the compact binary format (see [`crate::codec`])
+    pub fn to_bytes(&self) -> Vec<u8> {
+        crate::codec::encode_event(self)
+    }
+
+    /// Decode from the compact binary format (see [`crate::codec`])
+    pub fn from_bytes(bytes: &[u8]) -> crate::error::Result<Self> {
+        crate::codec::decode_event(bytes)
+    }
 }
 
 impl EventValue {
@@ -328,6 +338,25 @@ impl PyEtwEvent {
         self.inner.properties.contains_key(name)
     }
 
+    /// Get a property coerced to the type named by `spec` (e.g. `"int"`,
+    /// `"float"`, `"timestamp_fmt:%Y-%m-%d %H:%M:%S"`). Returns `None` if the
+    /// property is missing; raises `ValueError` if `spec` is unknown or the
+    /// value can't be coerced.
+    fn get_as(&self, py: Python<'_>, name: &str, spec: &str) -> PyResult<Option<PyObject>> {
+        let Some(value) = self.inner.properties.get(name) else {
+            return Ok(None);
+        };
+        let conversion: crate::conversion::Conversion = spec
+            .parse()
+            .map_err(|e: crate::conversion::ConversionError| {
+                pyo3::exceptions::PyValueError::new_err(e.to_string())
+            })?;
+        let converted = value
+            .convert(&conversion)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(Some(event_value_to_py(py, &converted)?))
+    }
+
     /// Get stack trace addresses (if captured)
     #[getter]
     fn stack_trace(&self, py: Python<'_>) -> Option<Py<PyList>> {
@@ -350,6 +379,17 @@ impl PyEtwEvent {
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
 
+    /// Encode to the compact binary format
+    fn to_bytes(&self, py: Python<'_>) -> Py<pyo3::types::PyBytes> {
+        pyo3::types::PyBytes::new(py, &self.inner.to_bytes()).into()
+    }
+
+    /// Decode from the compact binary format
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        Ok(EtwEvent::from_bytes(bytes)?.into())
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "EtwEvent(provider={}, event_id={}, pid={}, timestamp={})",
@@ -381,7 +421,7 @@ impl PyEtwEvent {
 }
 
 /// Convert EventValue to Python object
-fn event_value_to_py(py: Python<'_>, value: &EventValue) -> PyResult<PyObject> {
+pub(crate) fn event_value_to_py(py: Python<'_>, value: &EventValue) -> PyResult<PyObject> {
     Ok(match value {
         EventValue::Null => py.None(),
         EventValue::Bool(b) => b.into_pyobject(py)?.into_any().unbind(),
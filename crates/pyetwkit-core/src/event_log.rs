@@ -0,0 +1,363 @@
+//! Rotating on-disk event log
+//!
+//! Durably records `EtwEvent`s to an append-only, size/count-rotated log so
+//! long-running captures don't have to be held in memory. Each record is
+//! tagged with the `session_id` of the capture that produced it, so a single
+//! log directory can hold interleaved events from multiple sessions and
+//! still answer "give me everything from session N".
+
+use crate::error::Result;
+use crate::event::EtwEvent;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use pyo3::prelude::*;
+
+/// Configuration for a [`RotatingEventLog`]
+#[derive(Debug, Clone)]
+pub struct EventLogConfig {
+    /// Directory the log files live in (created if missing)
+    pub dir: PathBuf,
+    /// Roll over to a new file once the active file exceeds this many bytes
+    pub max_bytes_per_log: u64,
+    /// Delete the oldest rotated file once more than this many exist
+    pub max_log_count: usize,
+}
+
+impl Default for EventLogConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("pyetwkit-events"),
+            max_bytes_per_log: 64 * 1024 * 1024,
+            max_log_count: 10,
+        }
+    }
+}
+
+/// A single on-disk record: the session that produced the event plus the
+/// event itself, serialized with `EtwEvent`'s existing `Serialize` impl.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LogRecord {
+    session_id: u64,
+    timestamp: DateTime<Utc>,
+    event: EtwEvent,
+}
+
+/// Rotating append-only log of captured `EtwEvent`s
+pub struct RotatingEventLog {
+    config: EventLogConfig,
+    next_session_id: Arc<AtomicU64>,
+    active_file: Option<File>,
+    active_index: u64,
+    active_bytes: u64,
+}
+
+impl RotatingEventLog {
+    /// Open (or create) a rotating event log in `dir`
+    pub fn open(config: EventLogConfig) -> Result<Self> {
+        fs::create_dir_all(&config.dir)?;
+
+        let mut log = Self {
+            config,
+            next_session_id: Arc::new(AtomicU64::new(1)),
+            active_file: None,
+            active_index: 0,
+            active_bytes: 0,
+        };
+        log.active_index = log.next_free_index();
+        Ok(log)
+    }
+
+    /// Allocate a new session ID for a capture about to start
+    pub fn new_session_id(&self) -> u64 {
+        self.next_session_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Append an event under `session_id`, rotating/pruning as needed.
+    ///
+    /// I/O errors mark the current file broken and are swallowed (the
+    /// caller keeps tracing even if the disk is full); genuinely fatal
+    /// setup errors (e.g. can't create the log directory) still surface.
+    pub fn append(&mut self, session_id: u64, event: &EtwEvent) -> Result<()> {
+        let record = LogRecord {
+            session_id,
+            timestamp: event.timestamp,
+            event: event.clone(),
+        };
+        let payload = match serde_json::to_vec(&record) {
+            Ok(bytes) => bytes,
+            Err(_) => return Ok(()),
+        };
+
+        if self.active_file.is_none() {
+            if self.open_active_file().is_err() {
+                return Ok(());
+            }
+        }
+
+        let len = payload.len() as u32;
+        let write_result = (|| -> std::io::Result<()> {
+            let file = self.active_file.as_mut().expect("just opened");
+            file.write_all(&len.to_le_bytes())?;
+            file.write_all(&payload)?;
+            file.flush()
+        })();
+
+        match write_result {
+            Ok(()) => {
+                self.active_bytes += 4 + payload.len() as u64;
+                if self.active_bytes >= self.config.max_bytes_per_log {
+                    self.rotate()?;
+                }
+                Ok(())
+            }
+            Err(_) => {
+                // Broken file (e.g. full disk): drop it and try a fresh one
+                // next time rather than panicking mid-trace.
+                self.active_file = None;
+                Ok(())
+            }
+        }
+    }
+
+    /// Every distinct session ID that currently has at least one record on disk
+    pub fn sessions(&self) -> Result<Vec<u64>> {
+        let mut seen = std::collections::BTreeSet::new();
+        for path in self.log_files()? {
+            for record in Self::read_file(&path) {
+                seen.insert(record.session_id);
+            }
+        }
+        Ok(seen.into_iter().collect())
+    }
+
+    /// Replay every event recorded for `session_id`, in file order
+    pub fn read_session(&self, session_id: u64) -> Result<impl Iterator<Item = EtwEvent>> {
+        let mut events = Vec::new();
+        for path in self.log_files()? {
+            for record in Self::read_file(&path) {
+                if record.session_id == session_id {
+                    events.push(record.event);
+                }
+            }
+        }
+        Ok(events.into_iter())
+    }
+
+    fn open_active_file(&mut self) -> Result<()> {
+        let path = self.file_path(self.active_index);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        self.active_bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
+        self.active_file = Some(file);
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.active_file = None;
+        self.active_index += 1;
+        self.active_bytes = 0;
+        self.prune()
+    }
+
+    fn prune(&self) -> Result<()> {
+        let mut files = self.log_files()?;
+        while files.len() > self.config.max_log_count {
+            let oldest = files.remove(0);
+            let _ = fs::remove_file(oldest);
+        }
+        Ok(())
+    }
+
+    fn next_free_index(&self) -> u64 {
+        self.log_files()
+            .ok()
+            .and_then(|files| files.last().and_then(|p| Self::index_of(p)))
+            .unwrap_or(0)
+    }
+
+    fn file_path(&self, index: u64) -> PathBuf {
+        self.config.dir.join(format!("events-{index:010}.log"))
+    }
+
+    fn index_of(path: &Path) -> Option<u64> {
+        path.file_stem()?
+            .to_str()?
+            .strip_prefix("events-")?
+            .parse()
+            .ok()
+    }
+
+    /// All rotated log files, oldest first
+    fn log_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.config.dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| Self::index_of(path).is_some())
+            .collect();
+        files.sort_by_key(|p| Self::index_of(p).unwrap_or(0));
+        Ok(files)
+    }
+
+    /// Decode every record in a single log file, skipping a truncated or
+    /// corrupt tail instead of failing the whole scan.
+    fn read_file(path: &Path) -> Vec<LogRecord> {
+        let mut records = Vec::new();
+        let file = match File::open(path) {
+            Ok(f) => f,
+            Err(_) => return records,
+        };
+        let mut reader = BufReader::new(file);
+
+        loop {
+            let mut len_buf = [0u8; 4];
+            match reader.read_exact(&mut len_buf) {
+                Ok(()) => {}
+                Err(_) => break,
+            }
+            let len = u32::from_le_bytes(len_buf) as usize;
+            let mut payload = vec![0u8; len];
+            if reader.read_exact(&mut payload).is_err() {
+                break;
+            }
+            match serde_json::from_slice::<LogRecord>(&payload) {
+                Ok(record) => records.push(record),
+                Err(_) => break,
+            }
+        }
+
+        records
+    }
+}
+
+/// Python wrapper for `RotatingEventLog`
+#[pyclass(name = "EventLog")]
+pub struct PyEventLog {
+    inner: RotatingEventLog,
+    session_id: u64,
+}
+
+#[pymethods]
+impl PyEventLog {
+    /// Open (or create) a rotating event log, keyed to a fresh session ID
+    #[new]
+    #[pyo3(signature = (dir, max_bytes_per_log=64*1024*1024, max_log_count=10))]
+    fn new(dir: &str, max_bytes_per_log: u64, max_log_count: usize) -> PyResult<Self> {
+        let config = EventLogConfig {
+            dir: PathBuf::from(dir),
+            max_bytes_per_log,
+            max_log_count,
+        };
+        let inner = RotatingEventLog::open(config)?;
+        let session_id = inner.new_session_id();
+        Ok(Self { inner, session_id })
+    }
+
+    /// The session ID events appended through this handle are tagged with
+    #[getter]
+    fn session_id(&self) -> u64 {
+        self.session_id
+    }
+
+    /// Append an event to the log under this handle's session ID
+    fn append(&mut self, event: &crate::event::PyEtwEvent) -> PyResult<()> {
+        self.inner.append(self.session_id, event.inner())?;
+        Ok(())
+    }
+
+    /// List every session ID with recorded events
+    fn sessions(&self) -> PyResult<Vec<u64>> {
+        Ok(self.inner.sessions()?)
+    }
+
+    /// Read back all events recorded for a given session ID
+    fn read_session(&self, session_id: u64) -> PyResult<Vec<crate::event::PyEtwEvent>> {
+        Ok(self
+            .inner
+            .read_session(session_id)?
+            .map(crate::event::PyEtwEvent::from)
+            .collect())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EventLog(session_id={})", self.session_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pyetwkit-test-{name}-{}", Uuid::new_v4()));
+        dir
+    }
+
+    #[test]
+    fn test_append_and_read_session() {
+        let dir = temp_dir("append");
+        let mut log = RotatingEventLog::open(EventLogConfig {
+            dir: dir.clone(),
+            ..EventLogConfig::default()
+        })
+        .unwrap();
+
+        let session_id = log.new_session_id();
+        let event = EtwEvent::new(Uuid::new_v4(), 42);
+        log.append(session_id, &event).unwrap();
+
+        let events: Vec<_> = log.read_session(session_id).unwrap().collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_id, 42);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_rotation_and_pruning() {
+        let dir = temp_dir("rotate");
+        let mut log = RotatingEventLog::open(EventLogConfig {
+            dir: dir.clone(),
+            max_bytes_per_log: 1,
+            max_log_count: 2,
+        })
+        .unwrap();
+
+        let session_id = log.new_session_id();
+        for i in 0..5 {
+            let event = EtwEvent::new(Uuid::new_v4(), i);
+            log.append(session_id, &event).unwrap();
+        }
+
+        assert!(log.log_files().unwrap().len() <= 2);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_sessions_lists_distinct_ids() {
+        let dir = temp_dir("sessions");
+        let mut log = RotatingEventLog::open(EventLogConfig {
+            dir: dir.clone(),
+            ..EventLogConfig::default()
+        })
+        .unwrap();
+
+        let s1 = log.new_session_id();
+        let s2 = log.new_session_id();
+        log.append(s1, &EtwEvent::new(Uuid::new_v4(), 1)).unwrap();
+        log.append(s2, &EtwEvent::new(Uuid::new_v4(), 2)).unwrap();
+
+        let mut sessions = log.sessions().unwrap();
+        sessions.sort();
+        assert_eq!(sessions, vec![s1, s2]);
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
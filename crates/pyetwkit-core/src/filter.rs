@@ -1,5 +1,8 @@
 //! Event filtering
 
+use crate::event::EtwEvent;
+use crate::rules::PropertyPredicate;
+
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -13,13 +16,26 @@ pub enum EventFilter {
     Opcodes(Vec<u8>),
     /// Filter by process ID
     ProcessId(u32),
-    /// Filter by process name (substring match)
-    ProcessName(String),
+    /// Filter by process name: case-insensitive substring match by default,
+    /// or a regular expression when `is_regex` is set
+    ProcessName { pattern: String, is_regex: bool },
     /// Exclude specific event IDs
     ExcludeEventIds(Vec<u16>),
     /// Custom predicate (not serializable, uses Arc for Clone)
     #[serde(skip)]
     Custom(Arc<dyn Fn(u16, u8) -> bool + Send + Sync>),
+    /// Filter by a decoded event property (not serializable: holds an `EventValue`)
+    #[serde(skip)]
+    Property(PropertyPredicate),
+    /// Match only if every sub-filter matches (not serializable: may nest `Custom`)
+    #[serde(skip)]
+    All(Vec<EventFilter>),
+    /// Match if any sub-filter matches (not serializable: may nest `Custom`)
+    #[serde(skip)]
+    Any(Vec<EventFilter>),
+    /// Match if the sub-filter does not match (not serializable: may nest `Custom`)
+    #[serde(skip)]
+    Not(Box<EventFilter>),
 }
 
 impl Clone for EventFilter {
@@ -28,9 +44,16 @@ impl Clone for EventFilter {
             Self::EventIds(v) => Self::EventIds(v.clone()),
             Self::Opcodes(v) => Self::Opcodes(v.clone()),
             Self::ProcessId(v) => Self::ProcessId(*v),
-            Self::ProcessName(v) => Self::ProcessName(v.clone()),
+            Self::ProcessName { pattern, is_regex } => Self::ProcessName {
+                pattern: pattern.clone(),
+                is_regex: *is_regex,
+            },
             Self::ExcludeEventIds(v) => Self::ExcludeEventIds(v.clone()),
             Self::Custom(f) => Self::Custom(Arc::clone(f)),
+            Self::Property(p) => Self::Property(p.clone()),
+            Self::All(v) => Self::All(v.clone()),
+            Self::Any(v) => Self::Any(v.clone()),
+            Self::Not(v) => Self::Not(v.clone()),
         }
     }
 }
@@ -41,13 +64,31 @@ impl std::fmt::Debug for EventFilter {
             Self::EventIds(v) => f.debug_tuple("EventIds").field(v).finish(),
             Self::Opcodes(v) => f.debug_tuple("Opcodes").field(v).finish(),
             Self::ProcessId(v) => f.debug_tuple("ProcessId").field(v).finish(),
-            Self::ProcessName(v) => f.debug_tuple("ProcessName").field(v).finish(),
+            Self::ProcessName { pattern, is_regex } => f
+                .debug_struct("ProcessName")
+                .field("pattern", pattern)
+                .field("is_regex", is_regex)
+                .finish(),
             Self::ExcludeEventIds(v) => f.debug_tuple("ExcludeEventIds").field(v).finish(),
             Self::Custom(_) => f.debug_tuple("Custom").field(&"<fn>").finish(),
+            Self::Property(p) => f.debug_tuple("Property").field(p).finish(),
+            Self::All(v) => f.debug_tuple("All").field(v).finish(),
+            Self::Any(v) => f.debug_tuple("Any").field(v).finish(),
+            Self::Not(v) => f.debug_tuple("Not").field(v).finish(),
         }
     }
 }
 
+/// Context passed through [`EventFilter::matches_context`] so combinators can
+/// evaluate both event- and process-level sub-filters uniformly.
+#[derive(Debug, Clone, Copy)]
+pub struct MatchContext<'a> {
+    pub event_id: u16,
+    pub opcode: u8,
+    pub pid: u32,
+    pub process_name: Option<&'a str>,
+}
+
 impl EventFilter {
     /// Check if the filter matches the given event
     pub fn matches(&self, event_id: u16, opcode: u8) -> bool {
@@ -56,21 +97,129 @@ impl EventFilter {
             EventFilter::Opcodes(ops) => ops.contains(&opcode),
             EventFilter::ExcludeEventIds(ids) => !ids.contains(&event_id),
             EventFilter::Custom(f) => f(event_id, opcode),
-            // These need additional context, return true for now
-            EventFilter::ProcessId(_) | EventFilter::ProcessName(_) => true,
+            // These need additional context not available here; use
+            // `matches_context`/`matches_event` for a full evaluation.
+            EventFilter::ProcessId(_)
+            | EventFilter::ProcessName { .. }
+            | EventFilter::Property(_)
+            | EventFilter::All(_)
+            | EventFilter::Any(_)
+            | EventFilter::Not(_) => true,
         }
     }
 
-    /// Check if this filter matches a process
+    /// Check if this filter matches a process. `process_name` may be a bare
+    /// image name or a full path: [`EventFilter::ProcessName`] substring/regex
+    /// matching applies to whatever string is given.
     pub fn matches_process(&self, pid: u32, process_name: Option<&str>) -> bool {
         match self {
             EventFilter::ProcessId(filter_pid) => *filter_pid == pid,
-            EventFilter::ProcessName(name) => {
-                process_name.is_some_and(|pn| pn.to_lowercase().contains(&name.to_lowercase()))
-            }
+            EventFilter::ProcessName { pattern, is_regex } => process_name.is_some_and(|pn| {
+                if *is_regex {
+                    regex::Regex::new(pattern)
+                        .map(|re| re.is_match(pn))
+                        .unwrap_or(false)
+                } else {
+                    pn.to_lowercase().contains(&pattern.to_lowercase())
+                }
+            }),
             _ => true,
         }
     }
+
+    /// Combine filters with AND: matches only if every sub-filter matches
+    pub fn all(filters: impl IntoIterator<Item = EventFilter>) -> Self {
+        Self::All(filters.into_iter().collect())
+    }
+
+    /// Combine filters with OR: matches if any sub-filter matches
+    pub fn any(filters: impl IntoIterator<Item = EventFilter>) -> Self {
+        Self::Any(filters.into_iter().collect())
+    }
+
+    /// Negate a filter
+    pub fn negate(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+
+    /// Whether evaluating this filter (including nested combinators) needs a
+    /// resolved process name, so callers can skip the cost of
+    /// [`crate::process::ProcessResolver::resolve`]'s live lookup when
+    /// nothing in the filter actually asks for one
+    pub fn needs_process_name(&self) -> bool {
+        match self {
+            EventFilter::ProcessName { .. } => true,
+            EventFilter::All(filters) | EventFilter::Any(filters) => {
+                filters.iter().any(|f| f.needs_process_name())
+            }
+            EventFilter::Not(inner) => inner.needs_process_name(),
+            _ => false,
+        }
+    }
+
+    /// Evaluate this filter (including combinators) against full event and
+    /// process context. Unlike [`Self::matches`]/[`Self::matches_process`],
+    /// which only look at their own dimension, this dispatches each leaf
+    /// filter to whichever dimension it actually constrains.
+    pub fn matches_context(&self, ctx: &MatchContext<'_>) -> bool {
+        match self {
+            EventFilter::EventIds(_)
+            | EventFilter::Opcodes(_)
+            | EventFilter::ExcludeEventIds(_)
+            | EventFilter::Custom(_) => self.matches(ctx.event_id, ctx.opcode),
+            EventFilter::ProcessId(_) | EventFilter::ProcessName { .. } => {
+                self.matches_process(ctx.pid, ctx.process_name)
+            }
+            // Property filters need the decoded event; see `matches_event`.
+            EventFilter::Property(_) => true,
+            EventFilter::All(filters) => filters.iter().all(|f| f.matches_context(ctx)),
+            EventFilter::Any(filters) => filters.iter().any(|f| f.matches_context(ctx)),
+            EventFilter::Not(inner) => !inner.matches_context(ctx),
+        }
+    }
+
+    /// Evaluate this filter against a fully decoded event, including
+    /// [`EventFilter::Property`] sub-filters. Process name is taken from the
+    /// event's `ProcessName`/`ImageFileName` property, if present.
+    pub fn matches_event(&self, event: &EtwEvent) -> bool {
+        self.matches_event_with_process_name(event, None)
+    }
+
+    /// Like [`Self::matches_event`], but `resolved_process_name` (typically
+    /// looked up from a [`crate::process::ProcessResolver`] by PID) takes
+    /// precedence over the event's own decoded `ProcessName`/`ImageFileName`
+    /// property, for events that don't carry it themselves.
+    pub fn matches_event_with_process_name(
+        &self,
+        event: &EtwEvent,
+        resolved_process_name: Option<&str>,
+    ) -> bool {
+        match self {
+            EventFilter::Property(predicate) => predicate.matches(event),
+            EventFilter::All(filters) => filters
+                .iter()
+                .all(|f| f.matches_event_with_process_name(event, resolved_process_name)),
+            EventFilter::Any(filters) => filters
+                .iter()
+                .any(|f| f.matches_event_with_process_name(event, resolved_process_name)),
+            EventFilter::Not(inner) => {
+                !inner.matches_event_with_process_name(event, resolved_process_name)
+            }
+            _ => {
+                let own_process_name = event
+                    .get_string("ProcessName")
+                    .or_else(|| event.get_string("ImageFileName"));
+                let process_name = resolved_process_name.or(own_process_name.as_deref());
+                let ctx = MatchContext {
+                    event_id: event.event_id,
+                    opcode: event.opcode,
+                    pid: event.process_id,
+                    process_name,
+                };
+                self.matches_context(&ctx)
+            }
+        }
+    }
 }
 
 /// Filter builder for chaining multiple filters
@@ -105,9 +254,22 @@ impl FilterBuilder {
         self
     }
 
-    /// Filter by process name
+    /// Filter by process name (case-insensitive substring match)
     pub fn process_name(mut self, name: impl Into<String>) -> Self {
-        self.filters.push(EventFilter::ProcessName(name.into()));
+        self.filters.push(EventFilter::ProcessName {
+            pattern: name.into(),
+            is_regex: false,
+        });
+        self
+    }
+
+    /// Filter by process name using a regular expression instead of a
+    /// substring match
+    pub fn process_name_regex(mut self, pattern: impl Into<String>) -> Self {
+        self.filters.push(EventFilter::ProcessName {
+            pattern: pattern.into(),
+            is_regex: true,
+        });
         self
     }
 
@@ -118,6 +280,19 @@ impl FilterBuilder {
         self
     }
 
+    /// Filter by a decoded event property; evaluated via [`EventFilter::matches_event`]
+    pub fn property(mut self, predicate: PropertyPredicate) -> Self {
+        self.filters.push(EventFilter::Property(predicate));
+        self
+    }
+
+    /// Add an arbitrary filter, including combinators built with
+    /// [`EventFilter::all`]/[`EventFilter::any`]/[`EventFilter::negate`]
+    pub fn filter(mut self, filter: EventFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
     /// Build the filters
     pub fn build(self) -> Vec<EventFilter> {
         self.filters
@@ -131,15 +306,40 @@ impl FilterBuilder {
         pid: u32,
         process_name: Option<&str>,
     ) -> bool {
-        for filter in &self.filters {
-            if !filter.matches(event_id, opcode) {
-                return false;
-            }
-            if !filter.matches_process(pid, process_name) {
-                return false;
-            }
-        }
-        true
+        let ctx = MatchContext {
+            event_id,
+            opcode,
+            pid,
+            process_name,
+        };
+        self.filters.iter().all(|filter| filter.matches_context(&ctx))
+    }
+
+    /// Check if every filter matches the given decoded event, including
+    /// property predicates
+    pub fn matches_event(&self, event: &EtwEvent) -> bool {
+        self.filters.iter().all(|filter| filter.matches_event(event))
+    }
+
+    /// Like [`Self::matches_event`], but resolving the event's process name
+    /// via `resolver` for events that don't carry it themselves. The
+    /// resolver's live lookup is only performed when a filter actually needs
+    /// a process name, since it's considerably more expensive than the rest
+    /// of event matching and most filters never touch `ProcessName`.
+    pub fn matches_event_resolved(
+        &self,
+        event: &EtwEvent,
+        resolver: &crate::process::ProcessResolver,
+    ) -> bool {
+        let resolved = self
+            .filters
+            .iter()
+            .any(EventFilter::needs_process_name)
+            .then(|| resolver.resolve(event.process_id))
+            .flatten();
+        self.filters
+            .iter()
+            .all(|filter| filter.matches_event_with_process_name(event, resolved.as_deref()))
     }
 }
 
@@ -178,9 +378,22 @@ impl PyEventFilter {
         self.clone()
     }
 
-    /// Filter by process name (substring match)
+    /// Filter by process name (case-insensitive substring match)
     fn process_name(&mut self, name: String) -> Self {
-        self.filters.push(EventFilter::ProcessName(name));
+        self.filters.push(EventFilter::ProcessName {
+            pattern: name,
+            is_regex: false,
+        });
+        self.clone()
+    }
+
+    /// Filter by process name using a regular expression instead of a
+    /// substring match
+    fn process_name_regex(&mut self, pattern: String) -> Self {
+        self.filters.push(EventFilter::ProcessName {
+            pattern,
+            is_regex: true,
+        });
         self.clone()
     }
 
@@ -190,6 +403,15 @@ impl PyEventFilter {
         self.clone()
     }
 
+    /// Filter by a decoded property value. `op` is one of "equals"/"contains"/"gt"
+    fn property(&mut self, py: Python<'_>, name: &str, op: &str, value: Py<PyAny>) -> PyResult<Self> {
+        let op = crate::rules::predicate_op_from_str(op)?;
+        let value = crate::rules::py_value_to_event_value(value.bind(py))?;
+        self.filters
+            .push(EventFilter::Property(PropertyPredicate::new(name, op, value)));
+        Ok(self.clone())
+    }
+
     /// Check if the filter matches the given event
     fn matches(&self, event_id: u16, opcode: u8) -> bool {
         for filter in &self.filters {
@@ -200,14 +422,147 @@ impl PyEventFilter {
         true
     }
 
+    /// Check if the filter matches, considering process-level conditions too
+    #[pyo3(signature = (event_id, opcode, pid, process_name=None))]
+    fn matches_full(
+        &self,
+        event_id: u16,
+        opcode: u8,
+        pid: u32,
+        process_name: Option<&str>,
+    ) -> bool {
+        let ctx = MatchContext {
+            event_id,
+            opcode,
+            pid,
+            process_name,
+        };
+        self.filters.iter().all(|filter| filter.matches_context(&ctx))
+    }
+
+    /// Check if the filter matches a fully decoded event, including
+    /// property predicates added via `property(...)`
+    fn matches_event(&self, event: &crate::event::PyEtwEvent) -> bool {
+        self.filters
+            .iter()
+            .all(|filter| filter.matches_event(event.inner()))
+    }
+
+    /// Combine filters with AND: matches only if every filter matches
+    #[staticmethod]
+    fn all_of(filters: Vec<PyEventFilter>) -> Self {
+        let combined = EventFilter::all(filters.iter().map(PyEventFilter::as_combined));
+        Self {
+            filters: vec![combined],
+        }
+    }
+
+    /// Combine filters with OR: matches if any filter matches
+    #[staticmethod]
+    fn any_of(filters: Vec<PyEventFilter>) -> Self {
+        let combined = EventFilter::any(filters.iter().map(PyEventFilter::as_combined));
+        Self {
+            filters: vec![combined],
+        }
+    }
+
+    /// Negate this filter
+    fn negate(&self) -> Self {
+        Self {
+            filters: vec![self.as_combined().negate()],
+        }
+    }
+
+    /// Parse a filter expression, e.g. `event_id in (1, 2) and not pid == 4`
+    #[staticmethod]
+    fn from_str(expr: &str) -> PyResult<Self> {
+        let filter: EventFilter = expr
+            .parse()
+            .map_err(|e: crate::filter_dsl::FilterParseError| {
+                pyo3::exceptions::PyValueError::new_err(e.to_string())
+            })?;
+        Ok(Self {
+            filters: vec![filter],
+        })
+    }
+
     fn __repr__(&self) -> String {
         format!("EventFilter(count={})", self.filters.len())
     }
 }
 
+impl PyEventFilter {
+    /// Collapse the (implicitly AND-ed) filter list into a single
+    /// [`EventFilter`], wrapping in [`EventFilter::All`] if there's more than one.
+    fn as_combined(&self) -> EventFilter {
+        match self.filters.as_slice() {
+            [single] => single.clone(),
+            filters => EventFilter::All(filters.to_vec()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rules::PredicateOp;
+
+    fn event_with_property(name: &str, value: crate::event::EventValue) -> EtwEvent {
+        let mut event = EtwEvent::new(uuid::Uuid::new_v4(), 1);
+        event.properties.insert(name.to_string(), value);
+        event
+    }
+
+    #[test]
+    fn test_property_filter_matches_decoded_event() {
+        let filter = EventFilter::Property(PropertyPredicate::new(
+            "Duration",
+            PredicateOp::GreaterThan,
+            crate::event::EventValue::U32(1000),
+        ));
+
+        let fast = event_with_property("Duration", crate::event::EventValue::U32(500));
+        let slow = event_with_property("Duration", crate::event::EventValue::U32(5000));
+
+        assert!(!filter.matches_event(&fast));
+        assert!(filter.matches_event(&slow));
+    }
+
+    #[test]
+    fn test_property_filter_ignored_outside_matches_event() {
+        let filter = EventFilter::Property(PropertyPredicate::new(
+            "Duration",
+            PredicateOp::GreaterThan,
+            crate::event::EventValue::U32(1000),
+        ));
+
+        // Without a decoded event, property filters can't be evaluated and
+        // default to matching so they don't silently block unrelated checks.
+        let ctx = MatchContext {
+            event_id: 1,
+            opcode: 0,
+            pid: 0,
+            process_name: None,
+        };
+        assert!(filter.matches_context(&ctx));
+    }
+
+    #[test]
+    fn test_filter_builder_property_combines_with_event_id() {
+        let builder = FilterBuilder::new()
+            .event_ids([1])
+            .property(PropertyPredicate::new(
+                "Duration",
+                PredicateOp::GreaterThan,
+                crate::event::EventValue::U32(1000),
+            ));
+
+        let mut matching = EtwEvent::new(uuid::Uuid::new_v4(), 1);
+        matching
+            .properties
+            .insert("Duration".to_string(), crate::event::EventValue::U32(5000));
+        assert!(builder.build().iter().all(|f| f.matches_event(&matching)));
+    }
 
     #[test]
     fn test_event_id_filter() {
@@ -239,12 +594,37 @@ mod tests {
         assert!(filter.matches_process(1234, None));
         assert!(!filter.matches_process(5678, None));
 
-        let filter = EventFilter::ProcessName("chrome".to_string());
+        let filter = EventFilter::ProcessName {
+            pattern: "chrome".to_string(),
+            is_regex: false,
+        };
         assert!(filter.matches_process(0, Some("chrome.exe")));
         assert!(filter.matches_process(0, Some("Google Chrome")));
         assert!(!filter.matches_process(0, Some("firefox.exe")));
     }
 
+    #[test]
+    fn test_process_filter_regex() {
+        let filter = EventFilter::ProcessName {
+            pattern: r"^chrome\d*\.exe$".to_string(),
+            is_regex: true,
+        };
+        assert!(filter.matches_process(0, Some("chrome.exe")));
+        assert!(filter.matches_process(0, Some("chrome64.exe")));
+        assert!(!filter.matches_process(0, Some("not-chrome.exe")));
+        assert!(!filter.matches_process(0, Some("chrome.exe.bak")));
+    }
+
+    #[test]
+    fn test_process_filter_matches_full_image_path() {
+        let filter = EventFilter::ProcessName {
+            pattern: r"C:\\Program Files\\.*\\chrome\.exe".to_string(),
+            is_regex: true,
+        };
+        assert!(filter.matches_process(0, Some(r"C:\Program Files\Google\Chrome\chrome.exe")));
+        assert!(!filter.matches_process(0, Some(r"C:\Windows\System32\notepad.exe")));
+    }
+
     #[test]
     fn test_filter_builder() {
         let filters = FilterBuilder::new()
@@ -263,4 +643,58 @@ mod tests {
         assert!(!builder.matches_all(4, 0, 1000, None)); // Wrong event ID
         assert!(!builder.matches_all(1, 0, 2000, None)); // Wrong PID
     }
+
+    fn ctx(event_id: u16, opcode: u8, pid: u32, process_name: Option<&str>) -> MatchContext<'_> {
+        MatchContext {
+            event_id,
+            opcode,
+            pid,
+            process_name,
+        }
+    }
+
+    #[test]
+    fn test_any_combinator_matches_if_either_branch_matches() {
+        let filter = EventFilter::any([
+            EventFilter::EventIds(vec![1]),
+            EventFilter::ProcessId(1000),
+        ]);
+
+        assert!(filter.matches_context(&ctx(1, 0, 2000, None))); // event ID matches
+        assert!(filter.matches_context(&ctx(2, 0, 1000, None))); // PID matches
+        assert!(!filter.matches_context(&ctx(2, 0, 2000, None))); // neither matches
+    }
+
+    #[test]
+    fn test_all_combinator_requires_every_branch() {
+        let filter = EventFilter::all([
+            EventFilter::EventIds(vec![1]),
+            EventFilter::ProcessId(1000),
+        ]);
+
+        assert!(filter.matches_context(&ctx(1, 0, 1000, None)));
+        assert!(!filter.matches_context(&ctx(1, 0, 2000, None)));
+        assert!(!filter.matches_context(&ctx(2, 0, 1000, None)));
+    }
+
+    #[test]
+    fn test_not_combinator_inverts() {
+        let filter = EventFilter::EventIds(vec![1]).negate();
+
+        assert!(!filter.matches_context(&ctx(1, 0, 0, None)));
+        assert!(filter.matches_context(&ctx(2, 0, 0, None)));
+    }
+
+    #[test]
+    fn test_nested_combinators() {
+        // (EventIds([1]) OR EventIds([2])) AND NOT ProcessId(1000)
+        let filter = EventFilter::all([
+            EventFilter::any([EventFilter::EventIds(vec![1]), EventFilter::EventIds(vec![2])]),
+            EventFilter::ProcessId(1000).negate(),
+        ]);
+
+        assert!(filter.matches_context(&ctx(1, 0, 2000, None)));
+        assert!(!filter.matches_context(&ctx(1, 0, 1000, None)));
+        assert!(!filter.matches_context(&ctx(3, 0, 2000, None)));
+    }
 }
@@ -0,0 +1,432 @@
+//! String filter-expression DSL, compiling to [`EventFilter`]
+//!
+//! Lets callers describe a filter as text instead of chaining
+//! [`crate::filter::FilterBuilder`] calls, e.g.:
+//!
+//! ```text
+//! event_id in (1, 2, 3) and not pid == 4
+//! process_name contains "chrome" or event_id == 42
+//! ```
+//!
+//! Supported fields are `event_id`, `opcode`, `pid`, and `process_name`;
+//! operators are `==`, `!=`, `in (...)`, `contains`, and (for `process_name`
+//! only) `matches` for regular-expression matching; expressions combine with
+//! `and`/`or`/`not` and parentheses, with the usual precedence (`not` binds
+//! tighter than `and`, which binds tighter than `or`).
+
+use crate::filter::EventFilter;
+
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+use thiserror::Error;
+
+/// Error returned by [`EventFilter::from_str`](std::str::FromStr)
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FilterParseError {
+    #[error("unexpected character '{0}'")]
+    UnexpectedChar(char),
+    #[error("unterminated string literal")]
+    UnterminatedString,
+    #[error("invalid number: {0}")]
+    InvalidNumber(String),
+    #[error("unexpected end of expression")]
+    UnexpectedEof,
+    #[error("unexpected token: {0}")]
+    UnexpectedToken(String),
+    #[error("unknown field: {0}")]
+    UnknownField(String),
+    #[error("unknown operator '{0}' for field '{1}'")]
+    UnknownOperator(String, String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(i64),
+    Str(String),
+    LParen,
+    RParen,
+    Comma,
+    Eq,
+    Ne,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, FilterParseError> {
+    let mut chars: Peekable<Chars<'_>> = input.chars().peekable();
+    let mut tokens = Vec::new();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' | '\n' | '\r' => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            ',' => {
+                chars.next();
+                tokens.push(Token::Comma);
+            }
+            '=' => {
+                chars.next();
+                if chars.peek() == Some(&'=') {
+                    chars.next();
+                }
+                tokens.push(Token::Eq);
+            }
+            '!' => {
+                chars.next();
+                if chars.peek() == Some(&'=') {
+                    chars.next();
+                }
+                tokens.push(Token::Ne);
+            }
+            '"' => {
+                chars.next();
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => s.push(c),
+                        None => return Err(FilterParseError::UnterminatedString),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            c if c.is_ascii_digit() => {
+                let mut s = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_digit() {
+                        s.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let n = s
+                    .parse()
+                    .map_err(|_| FilterParseError::InvalidNumber(s.clone()))?;
+                tokens.push(Token::Number(n));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut s = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' {
+                        s.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(s));
+            }
+            other => return Err(FilterParseError::UnexpectedChar(other)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect_ident(&mut self, expected: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(s)) if s.eq_ignore_ascii_case(expected)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// `or_expr := and_expr ("or" and_expr)*`
+    fn parse_or(&mut self) -> Result<EventFilter, FilterParseError> {
+        let mut filters = vec![self.parse_and()?];
+        while self.expect_ident("or") {
+            filters.push(self.parse_and()?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.pop().unwrap()
+        } else {
+            EventFilter::any(filters)
+        })
+    }
+
+    /// `and_expr := unary ("and" unary)*`
+    fn parse_and(&mut self) -> Result<EventFilter, FilterParseError> {
+        let mut filters = vec![self.parse_unary()?];
+        while self.expect_ident("and") {
+            filters.push(self.parse_unary()?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.pop().unwrap()
+        } else {
+            EventFilter::all(filters)
+        })
+    }
+
+    /// `unary := "not" unary | atom`
+    fn parse_unary(&mut self) -> Result<EventFilter, FilterParseError> {
+        if self.expect_ident("not") {
+            return Ok(self.parse_unary()?.negate());
+        }
+        self.parse_atom()
+    }
+
+    /// `atom := "(" or_expr ")" | predicate`
+    fn parse_atom(&mut self) -> Result<EventFilter, FilterParseError> {
+        if matches!(self.peek(), Some(Token::LParen)) {
+            self.pos += 1;
+            let filter = self.parse_or()?;
+            match self.next() {
+                Some(Token::RParen) => Ok(filter),
+                Some(other) => Err(FilterParseError::UnexpectedToken(format!("{other:?}"))),
+                None => Err(FilterParseError::UnexpectedEof),
+            }
+        } else {
+            self.parse_predicate()
+        }
+    }
+
+    /// `predicate := field op value`
+    fn parse_predicate(&mut self) -> Result<EventFilter, FilterParseError> {
+        let field = match self.next() {
+            Some(Token::Ident(s)) => s,
+            Some(other) => return Err(FilterParseError::UnexpectedToken(format!("{other:?}"))),
+            None => return Err(FilterParseError::UnexpectedEof),
+        };
+
+        match field.as_str() {
+            "event_id" | "opcode" => self.parse_numeric_predicate(&field),
+            "pid" => {
+                self.expect_op(&field, Token::Eq)?;
+                let n = self.parse_number()?;
+                Ok(EventFilter::ProcessId(n as u32))
+            }
+            "process_name" => {
+                if self.expect_ident("contains") {
+                    let s = self.parse_string()?;
+                    Ok(EventFilter::ProcessName {
+                        pattern: s,
+                        is_regex: false,
+                    })
+                } else if self.expect_ident("matches") {
+                    let s = self.parse_string()?;
+                    Ok(EventFilter::ProcessName {
+                        pattern: s,
+                        is_regex: true,
+                    })
+                } else if matches!(self.peek(), Some(Token::Eq)) {
+                    self.pos += 1;
+                    let s = self.parse_string()?;
+                    Ok(EventFilter::ProcessName {
+                        pattern: s,
+                        is_regex: false,
+                    })
+                } else {
+                    Err(FilterParseError::UnknownOperator(
+                        format!("{:?}", self.peek()),
+                        field,
+                    ))
+                }
+            }
+            other => Err(FilterParseError::UnknownField(other.to_string())),
+        }
+    }
+
+    fn parse_numeric_predicate(&mut self, field: &str) -> Result<EventFilter, FilterParseError> {
+        if self.expect_ident("in") {
+            let ids = self.parse_number_list()?;
+            return Ok(match field {
+                "event_id" => EventFilter::EventIds(ids.iter().map(|&n| n as u16).collect()),
+                "opcode" => EventFilter::Opcodes(ids.iter().map(|&n| n as u8).collect()),
+                _ => unreachable!(),
+            });
+        }
+
+        match self.next() {
+            Some(Token::Eq) => {
+                let n = self.parse_number()?;
+                Ok(match field {
+                    "event_id" => EventFilter::EventIds(vec![n as u16]),
+                    "opcode" => EventFilter::Opcodes(vec![n as u8]),
+                    _ => unreachable!(),
+                })
+            }
+            Some(Token::Ne) => {
+                let n = self.parse_number()?;
+                Ok(match field {
+                    "event_id" => EventFilter::ExcludeEventIds(vec![n as u16]),
+                    _ => {
+                        return Err(FilterParseError::UnknownOperator(
+                            "!=".to_string(),
+                            field.to_string(),
+                        ))
+                    }
+                })
+            }
+            Some(other) => Err(FilterParseError::UnexpectedToken(format!("{other:?}"))),
+            None => Err(FilterParseError::UnexpectedEof),
+        }
+    }
+
+    fn parse_number_list(&mut self) -> Result<Vec<i64>, FilterParseError> {
+        match self.next() {
+            Some(Token::LParen) => {}
+            Some(other) => return Err(FilterParseError::UnexpectedToken(format!("{other:?}"))),
+            None => return Err(FilterParseError::UnexpectedEof),
+        }
+
+        let mut numbers = vec![self.parse_number()?];
+        while matches!(self.peek(), Some(Token::Comma)) {
+            self.pos += 1;
+            numbers.push(self.parse_number()?);
+        }
+
+        match self.next() {
+            Some(Token::RParen) => Ok(numbers),
+            Some(other) => Err(FilterParseError::UnexpectedToken(format!("{other:?}"))),
+            None => Err(FilterParseError::UnexpectedEof),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<i64, FilterParseError> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(n),
+            Some(other) => Err(FilterParseError::UnexpectedToken(format!("{other:?}"))),
+            None => Err(FilterParseError::UnexpectedEof),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, FilterParseError> {
+        match self.next() {
+            Some(Token::Str(s)) => Ok(s),
+            Some(other) => Err(FilterParseError::UnexpectedToken(format!("{other:?}"))),
+            None => Err(FilterParseError::UnexpectedEof),
+        }
+    }
+
+    fn expect_op(&mut self, field: &str, token: Token) -> Result<(), FilterParseError> {
+        match self.next() {
+            Some(t) if t == token => Ok(()),
+            Some(other) => Err(FilterParseError::UnknownOperator(
+                format!("{other:?}"),
+                field.to_string(),
+            )),
+            None => Err(FilterParseError::UnexpectedEof),
+        }
+    }
+}
+
+impl FromStr for EventFilter {
+    type Err = FilterParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let filter = parser.parse_or()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(FilterParseError::UnexpectedToken(format!(
+                "{:?}",
+                parser.tokens[parser.pos]
+            )));
+        }
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::MatchContext;
+
+    fn ctx(event_id: u16, opcode: u8, pid: u32, process_name: Option<&str>) -> MatchContext<'_> {
+        MatchContext {
+            event_id,
+            opcode,
+            pid,
+            process_name,
+        }
+    }
+
+    #[test]
+    fn test_parse_simple_equality() {
+        let filter: EventFilter = "event_id == 42".parse().unwrap();
+        assert!(filter.matches_context(&ctx(42, 0, 0, None)));
+        assert!(!filter.matches_context(&ctx(1, 0, 0, None)));
+    }
+
+    #[test]
+    fn test_parse_in_list() {
+        let filter: EventFilter = "event_id in (1, 2, 3)".parse().unwrap();
+        assert!(filter.matches_context(&ctx(2, 0, 0, None)));
+        assert!(!filter.matches_context(&ctx(4, 0, 0, None)));
+    }
+
+    #[test]
+    fn test_parse_contains_and_and() {
+        let filter: EventFilter = "event_id == 1 and process_name contains \"chrome\""
+            .parse()
+            .unwrap();
+        assert!(filter.matches_context(&ctx(1, 0, 0, Some("chrome.exe"))));
+        assert!(!filter.matches_context(&ctx(1, 0, 0, Some("firefox.exe"))));
+        assert!(!filter.matches_context(&ctx(2, 0, 0, Some("chrome.exe"))));
+    }
+
+    #[test]
+    fn test_parse_or_and_not_with_parens() {
+        let filter: EventFilter = "not (pid == 1000 or pid == 2000)".parse().unwrap();
+        assert!(filter.matches_context(&ctx(0, 0, 3000, None)));
+        assert!(!filter.matches_context(&ctx(0, 0, 1000, None)));
+        assert!(!filter.matches_context(&ctx(0, 0, 2000, None)));
+    }
+
+    #[test]
+    fn test_parse_not_exceeds_and_precedence() {
+        // not event_id == 1 and pid == 5  =>  (not event_id == 1) and pid == 5
+        let filter: EventFilter = "not event_id == 1 and pid == 5".parse().unwrap();
+        assert!(filter.matches_context(&ctx(2, 0, 5, None)));
+        assert!(!filter.matches_context(&ctx(1, 0, 5, None)));
+        assert!(!filter.matches_context(&ctx(2, 0, 6, None)));
+    }
+
+    #[test]
+    fn test_parse_process_name_matches_regex() {
+        let filter: EventFilter = "process_name matches \"^chrome\\d*\\.exe$\""
+            .parse()
+            .unwrap();
+        assert!(filter.matches_context(&ctx(1, 0, 0, Some("chrome64.exe"))));
+        assert!(!filter.matches_context(&ctx(1, 0, 0, Some("firefox.exe"))));
+    }
+
+    #[test]
+    fn test_parse_unknown_field_errors() {
+        assert_eq!(
+            "bogus == 1".parse::<EventFilter>(),
+            Err(FilterParseError::UnknownField("bogus".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_trailing_garbage_errors() {
+        assert!("event_id == 1 )".parse::<EventFilter>().is_err());
+    }
+}
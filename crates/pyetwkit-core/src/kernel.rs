@@ -192,15 +192,20 @@ impl KernelSession {
 
         // Create callback
         let callback = move |record: &EventRecord, schema_locator: &SchemaLocator| {
-            stats.record_event_received();
+            let provider_id = crate::session::guid_to_uuid(record.provider_id());
+            stats.record_event_received(provider_id);
 
             // Parse event
-            let event = crate::session::parse_event_record(record, schema_locator.event_schema(record).as_ref());
+            let event = crate::session::parse_event_record(
+                record,
+                schema_locator.event_schema(record).as_ref(),
+                None,
+            );
 
             // Send to channel
             match event_tx.try_send(event) {
                 Ok(_) => {
-                    stats.record_event_processed();
+                    stats.record_event_processed(provider_id);
                 }
                 Err(TrySendError::Full(_)) => {
                     stats.record_events_lost(1);
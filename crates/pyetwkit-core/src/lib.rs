@@ -7,27 +7,44 @@
 //! - Provider discovery and enumeration
 //! - Python bindings via pyo3
 
+pub mod activity;
+pub mod codec;
+pub mod conversion;
 pub mod discovery;
 pub mod error;
 pub mod etl_reader;
 pub mod event;
+pub mod event_log;
 pub mod filter;
+pub mod filter_dsl;
 pub mod kernel;
+pub mod process;
 pub mod provider;
+pub mod rules;
 pub mod schema;
+pub mod schema_registry;
+pub mod schema_resolver;
 pub mod session;
+pub mod spill;
 pub mod stats;
+pub mod tlg;
 
 // Re-export main types
+pub use activity::ActivityTracker;
 pub use discovery::{
     get_provider_info, list_providers, search_providers, ProviderDetails, ProviderInfo,
 };
+pub use conversion::{Conversion, ConversionError};
 pub use error::{EtwError, Result};
-pub use etl_reader::EtlReader;
+pub use etl_reader::{EtlReader, MultiEtlReader};
 pub use event::EtwEvent;
+pub use event_log::RotatingEventLog;
 pub use filter::EventFilter;
+pub use filter_dsl::FilterParseError;
 pub use kernel::{KernelEventCategory, KernelSession, KernelSessionConfig};
+pub use process::ProcessResolver;
 pub use provider::EtwProvider;
+pub use rules::{Diagnostic, PredicateOp, PropertyPredicate, Rule, RuleSet, Severity};
 pub use session::{EtwSession, SessionConfig, TraceMode};
 pub use stats::SessionStats;
 
@@ -66,6 +83,11 @@ fn pyetwkit_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // Register ETL reader
     m.add_class::<etl_reader::PyEtlReader>()?;
+    m.add_class::<etl_reader::PyEtlReaderStats>()?;
+    m.add_class::<etl_reader::PyMultiEtlReader>()?;
+
+    // Register rotating event log
+    m.add_class::<event_log::PyEventLog>()?;
 
     // Register EnableProperty enum
     m.add_class::<provider::PyEnableProperty>()?;
@@ -74,6 +96,18 @@ fn pyetwkit_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<schema::PyEventSchema>()?;
     m.add_class::<schema::PyPropertyInfo>()?;
     m.add_class::<schema::PySchemaCache>()?;
+    m.add_class::<schema_resolver::PySchemaResolver>()?;
+
+    // Register rule-based alerting classes
+    m.add_class::<rules::PyRule>()?;
+    m.add_class::<rules::PyDiagnostic>()?;
+    m.add_class::<rules::PyRuleSet>()?;
+
+    // Register activity-correlation tracker
+    m.add_class::<activity::PyActivityTracker>()?;
+
+    // Register process resolver
+    m.add_class::<process::PyProcessResolver>()?;
 
     // Register submodules
     let raw_module = PyModule::new_bound(m.py(), "raw")?;
@@ -0,0 +1,245 @@
+//! PID → process-name resolution
+//!
+//! Live process filters (`EventFilter::ProcessId`/`ProcessName`) need a
+//! PID → image-name map that most ETW events don't carry themselves — only
+//! the kernel Process provider's start/stop events do. [`ProcessResolver`]
+//! seeds that map from a snapshot of already-running processes, then keeps
+//! it current by consuming those start/stop events as they arrive.
+
+use crate::error::{EtwError, Result};
+use crate::event::EtwEvent;
+
+use parking_lot::RwLock;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use windows::core::PWSTR;
+use windows::Win32::Foundation::CloseHandle;
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+    TH32CS_SNAPPROCESS,
+};
+use windows::Win32::System::Threading::{
+    OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+
+/// Opcode used by the NT Kernel Logger's Process provider for process exit
+const PROCESS_STOP_OPCODE: u8 = 2;
+
+/// How long a [`ProcessResolver::resolve`] result (positive or negative) is
+/// cached before a repeat lookup for the same PID re-issues the live
+/// `OpenProcess`/`QueryFullProcessImageNameW` syscalls. Bounds the syscall
+/// rate on the per-event filtering hot path without letting a resolved name
+/// go stale for long after a process exits and its PID is reused.
+const RESOLVE_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// PID → image-name mapping, kept current by consuming kernel Process
+/// start/stop events (and seeded from a snapshot of already-running processes)
+#[derive(Debug, Default)]
+pub struct ProcessResolver {
+    names: RwLock<HashMap<u32, String>>,
+    /// Short-TTL cache of [`full_image_path`] results, keyed by PID, so
+    /// repeated `resolve()` calls for the same PID within `RESOLVE_CACHE_TTL`
+    /// skip the live syscalls
+    resolve_cache: RwLock<HashMap<u32, (Option<String>, Instant)>>,
+}
+
+impl ProcessResolver {
+    /// Create an empty resolver, without seeding from a process snapshot
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Create a resolver seeded with a snapshot of currently running processes
+    pub fn new() -> Self {
+        let resolver = Self::empty();
+        if let Ok(snapshot) = snapshot_processes() {
+            resolver.names.write().extend(snapshot);
+        }
+        resolver
+    }
+
+    /// Consume a kernel Process start/stop event, updating the PID → name
+    /// map. Events without an `ImageFileName` property (i.e. not Process
+    /// provider events) are ignored.
+    pub fn record(&self, event: &EtwEvent) {
+        let Some(image_name) = event.get_string("ImageFileName") else {
+            return;
+        };
+
+        if event.opcode == PROCESS_STOP_OPCODE {
+            self.names.write().remove(&event.process_id);
+        } else {
+            self.names.write().insert(event.process_id, image_name);
+        }
+        // The PID's identity just changed; don't let a cached `resolve()`
+        // result from before this event outlive it.
+        self.resolve_cache.write().remove(&event.process_id);
+    }
+
+    /// Look up the resolved image name for a PID, if known. Tries the full
+    /// image path first (so `ProcessName` filters can match against it),
+    /// falling back to whatever name was captured from a start event. The
+    /// result (including a negative one) is cached for `RESOLVE_CACHE_TTL`
+    /// so a hot filtering loop calling this once per event doesn't re-issue
+    /// the live syscalls for every single event from the same process.
+    pub fn resolve(&self, pid: u32) -> Option<String> {
+        if let Some(cached) = self.cached_resolution(pid) {
+            return cached;
+        }
+
+        let resolved = full_image_path(pid).or_else(|| self.names.read().get(&pid).cloned());
+        self.resolve_cache
+            .write()
+            .insert(pid, (resolved.clone(), Instant::now()));
+        resolved
+    }
+
+    /// A still-fresh cached `resolve()` result for `pid`, if one exists.
+    /// Returns `None` (not `Some(None)`) when there's no usable cache entry,
+    /// distinct from a cached negative result.
+    fn cached_resolution(&self, pid: u32) -> Option<Option<String>> {
+        let cache = self.resolve_cache.read();
+        let (name, cached_at) = cache.get(&pid)?;
+        (cached_at.elapsed() < RESOLVE_CACHE_TTL).then(|| name.clone())
+    }
+
+    /// Number of known PID → name mappings
+    pub fn len(&self) -> usize {
+        self.names.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.read().is_empty()
+    }
+}
+
+/// Shared resolver handle, passed into session/reader processing threads
+pub type SharedProcessResolver = Arc<ProcessResolver>;
+
+/// Snapshot the currently running processes as a PID → image-name map
+fn snapshot_processes() -> Result<HashMap<u32, String>> {
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0)
+            .map_err(|e| EtwError::WindowsError(e.message().to_string(), e.code().0 as u32))?;
+
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+
+        let mut processes = HashMap::new();
+        if Process32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                processes.insert(entry.th32ProcessID, read_exe_name(&entry.szExeFile));
+                if Process32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+
+        let _ = CloseHandle(snapshot);
+        Ok(processes)
+    }
+}
+
+/// Resolve a PID's full image path via `QueryFullProcessImageNameW`
+fn full_image_path(pid: u32) -> Option<String> {
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut buf = [0u16; 512];
+        let mut size = buf.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            handle,
+            PROCESS_NAME_WIN32,
+            PWSTR(buf.as_mut_ptr()),
+            &mut size,
+        );
+        let _ = CloseHandle(handle);
+        result.ok()?;
+        Some(String::from_utf16_lossy(&buf[..size as usize]))
+    }
+}
+
+/// Read a null-terminated wide string out of a fixed-size buffer
+fn read_exe_name(buf: &[u16]) -> String {
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..len])
+}
+
+/// Python wrapper for [`ProcessResolver`]
+#[pyclass(name = "ProcessResolver")]
+pub struct PyProcessResolver {
+    inner: SharedProcessResolver,
+}
+
+#[pymethods]
+impl PyProcessResolver {
+    /// Create a resolver seeded with a snapshot of currently running processes
+    #[new]
+    fn new() -> Self {
+        Self {
+            inner: Arc::new(ProcessResolver::new()),
+        }
+    }
+
+    /// Record a kernel Process start/stop event, updating the PID → name map
+    fn record(&self, event: &crate::event::PyEtwEvent) {
+        self.inner.record(event.inner());
+    }
+
+    /// Resolve the image name (full path, where available) for a PID
+    fn resolve(&self, pid: u32) -> Option<String> {
+        self.inner.resolve(pid)
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ProcessResolver(known={})", self.inner.len())
+    }
+}
+
+impl PyProcessResolver {
+    /// Shared handle to the underlying resolver, for wiring into other
+    /// Rust-side components (e.g. [`crate::etl_reader::PyEtlReader`])
+    pub(crate) fn shared(&self) -> SharedProcessResolver {
+        Arc::clone(&self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn process_event(pid: u32, opcode: u8, image_name: &str) -> EtwEvent {
+        let mut event = EtwEvent::new(Uuid::new_v4(), 1);
+        event.process_id = pid;
+        event.opcode = opcode;
+        event
+            .properties
+            .insert("ImageFileName".to_string(), crate::event::EventValue::String(image_name.to_string()));
+        event
+    }
+
+    #[test]
+    fn test_record_ignores_events_without_image_name() {
+        let resolver = ProcessResolver::empty();
+        resolver.record(&EtwEvent::new(Uuid::new_v4(), 1));
+        assert!(resolver.is_empty());
+    }
+
+    #[test]
+    fn test_record_start_then_stop() {
+        let resolver = ProcessResolver::empty();
+        resolver.record(&process_event(1234, 1, "notepad.exe"));
+        assert_eq!(resolver.len(), 1);
+
+        resolver.record(&process_event(1234, PROCESS_STOP_OPCODE, "notepad.exe"));
+        assert!(resolver.is_empty());
+    }
+}
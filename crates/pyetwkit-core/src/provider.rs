@@ -1,7 +1,9 @@
 //! ETW Provider management
 
+use crate::discovery;
 use crate::error::{EtwError, Result};
 use crate::filter::EventFilter;
+use crate::schema_registry::{self, field_type_from_str, FieldType};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -61,8 +63,14 @@ pub struct EtwProvider {
     pub filters: Vec<EventFilter>,
     /// Whether the provider is enabled
     pub enabled: bool,
-    /// Capture stack traces for events
-    pub capture_stack: bool,
+    /// `EVENT_ENABLE_PROPERTY_*` bitmask passed as `EnableParameters.EnableProperty`
+    /// when the provider is enabled, ORing together [`EnableProperty`] flags
+    /// (stack traces, SID, TS ID, process start key)
+    pub enable_properties: u32,
+    /// NT Kernel Logger flag group (`EVENT_TRACE_FLAG_*`). Zero means this
+    /// isn't a kernel provider; non-zero means it's selected by flag group
+    /// rather than by keyword mask (see [`EtwProvider::matches_event`]).
+    pub kernel_flags: u32,
 }
 
 impl EtwProvider {
@@ -76,7 +84,8 @@ impl EtwProvider {
             keywords_all: 0,
             filters: Vec::new(),
             enabled: true,
-            capture_stack: false,
+            enable_properties: 0,
+            kernel_flags: 0,
         }
     }
 
@@ -87,6 +96,19 @@ impl EtwProvider {
         Ok(Self::by_guid(guid))
     }
 
+    /// Construct a provider by friendly name (e.g.
+    /// `"Microsoft-Windows-PowerShell"`), looked up case-insensitively in the
+    /// system's registered-provider catalog via
+    /// [`discovery::list_providers`]. Returns [`EtwError::ProviderNotFound`]
+    /// if no registered provider matches.
+    pub fn from_name(name: &str) -> Result<Self> {
+        let found = discovery::list_providers()?
+            .into_iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| EtwError::ProviderNotFound(name.to_string()))?;
+        Ok(Self::by_guid(found.guid).with_name(found.name))
+    }
+
     /// Set provider name
     pub fn with_name(mut self, name: impl Into<String>) -> Self {
         self.name = Some(name.into());
@@ -117,14 +139,72 @@ impl EtwProvider {
         self
     }
 
-    /// Enable stack trace capture
+    /// Set the full `EVENT_ENABLE_PROPERTY_*` bitmask passed as
+    /// `EnableParameters.EnableProperty`
+    pub fn with_enable_properties(mut self, flags: u32) -> Self {
+        self.enable_properties = flags;
+        self
+    }
+
+    /// Enable or disable stack trace capture, a convenience wrapper that
+    /// sets/clears just the [`EnableProperty::StackTrace`] bit of
+    /// `enable_properties`
     pub fn with_stack_trace(mut self, capture: bool) -> Self {
-        self.capture_stack = capture;
+        if capture {
+            self.enable_properties |= EnableProperty::StackTrace.value();
+        } else {
+            self.enable_properties &= !EnableProperty::StackTrace.value();
+        }
+        self
+    }
+
+    /// Set the NT Kernel Logger flag group this provider captures, e.g.
+    /// [`KernelFlag::Process`] `|` [`KernelFlag::Thread`]. Typically paired
+    /// with `by_guid(kernel_providers::NT_KERNEL_LOGGER)`.
+    ///
+    /// The NT Kernel Logger only talks `EVENT_TRACE_FLAG_*` groups through
+    /// `KernelTrace`, not `UserTrace`'s `Provider::by_guid`/`EnableTraceEx2`
+    /// path, so a kernel-flagged provider can't be added to an
+    /// [`crate::session::EtwSession`] — `EtwSession::start` rejects it with
+    /// [`EtwError::InvalidConfig`]. Use [`crate::kernel::KernelSession`] to
+    /// actually capture kernel events.
+    pub fn with_kernel_flags(mut self, flags: u32) -> Self {
+        self.kernel_flags = flags;
         self
     }
 
+    /// Resolve keyword names (e.g. `"Runspace"`, `"Pipeline"`) against this
+    /// provider's manifest via TDH (`TdhEnumerateProviderFieldInformation`),
+    /// OR-ing together the resulting masks, pywintrace-style. Returns
+    /// [`EtwError::KeywordNotFound`] if a name isn't in the manifest.
+    pub fn resolve_keywords(&self, names: &[&str]) -> Result<u64> {
+        resolve_field_mask(&self.guid, discovery::EVENT_FIELD_KEYWORD, names)
+    }
+
+    /// Resolve a level name (e.g. `"Error"`, `"Warning"`) against this
+    /// provider's manifest via TDH, the same way
+    /// [`EtwProvider::resolve_keywords`] resolves keyword names.
+    pub fn resolve_level(&self, name: &str) -> Result<TraceLevel> {
+        let value = resolve_field_mask(&self.guid, discovery::EVENT_FIELD_LEVEL, &[name])?;
+        Ok(TraceLevel::from(value as u8))
+    }
+
+    /// Register a declarative field layout for events from this provider
+    /// that TDH can't parse (see [`crate::schema_registry`]), keyed by event
+    /// id and version.
+    pub fn register_schema(&self, event_id: u16, version: u8, fields: Vec<(String, FieldType)>) {
+        schema_registry::register_schema(self.guid, event_id, version, fields);
+    }
+
     /// Check if this provider matches the given event criteria
     pub fn matches_event(&self, event_id: u16, opcode: u8, level: u8, keywords: u64) -> bool {
+        // Kernel providers are selected by EVENT_TRACE_FLAG_* group, not by
+        // level/keyword mask: the event's `keywords` carries the flag group
+        // the NT Kernel Logger tagged it with.
+        if self.kernel_flags != 0 {
+            return self.kernel_flags as u64 & keywords != 0;
+        }
+
         // Level filter
         if level > self.level as u8 {
             return false;
@@ -149,6 +229,31 @@ impl EtwProvider {
     }
 }
 
+fn resolve_field_mask(guid: &Uuid, field_type: i32, names: &[&str]) -> Result<u64> {
+    let fields = discovery::provider_fields(guid, field_type)?;
+    let mut mask = 0u64;
+    for name in names {
+        let value = fields
+            .get(&name.to_lowercase())
+            .ok_or_else(|| EtwError::KeywordNotFound(name.to_string()))?;
+        mask |= value;
+    }
+    Ok(mask)
+}
+
+/// Accept either a raw `u64` bitmask or a list of keyword names from
+/// Python, resolving names against the provider's manifest via TDH
+fn keywords_arg_to_mask(provider: &EtwProvider, value: &Bound<'_, PyAny>) -> PyResult<u64> {
+    if let Ok(mask) = value.extract::<u64>() {
+        return Ok(mask);
+    }
+    let names: Vec<String> = value.extract().map_err(|_| {
+        pyo3::exceptions::PyTypeError::new_err("keywords must be an int or a list of keyword names")
+    })?;
+    let refs: Vec<&str> = names.iter().map(String::as_str).collect();
+    Ok(provider.resolve_keywords(&refs)?)
+}
+
 /// Enable properties for tracing (e.g., stack traces)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EnableProperty {
@@ -210,6 +315,47 @@ impl PyEnableProperty {
     }
 }
 
+/// NT Kernel Logger flag groups (`EVENT_TRACE_FLAG_*`), configuring which
+/// classic kernel events the NT Kernel Logger session captures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KernelFlag {
+    /// Process creation/deletion
+    Process,
+    /// Thread creation/deletion
+    Thread,
+    /// Image (DLL/EXE) load
+    ImageLoad,
+    /// Disk I/O
+    DiskIo,
+    /// Network TCP/IP
+    NetworkTcpip,
+    /// Registry access
+    Registry,
+}
+
+impl KernelFlag {
+    /// Get the Windows `EVENT_TRACE_FLAG_*` constant value
+    pub fn value(&self) -> u32 {
+        match self {
+            KernelFlag::Process => 0x00000001,      // EVENT_TRACE_FLAG_PROCESS
+            KernelFlag::Thread => 0x00000002,        // EVENT_TRACE_FLAG_THREAD
+            KernelFlag::ImageLoad => 0x00000004,     // EVENT_TRACE_FLAG_IMAGE_LOAD
+            KernelFlag::DiskIo => 0x00000100,        // EVENT_TRACE_FLAG_DISK_IO
+            KernelFlag::NetworkTcpip => 0x00010000,  // EVENT_TRACE_FLAG_NETWORK_TCPIP
+            KernelFlag::Registry => 0x00020000,      // EVENT_TRACE_FLAG_REGISTRY
+        }
+    }
+}
+
+/// The NT Kernel Logger, a special ETW session selected by `EVENT_TRACE_FLAG_*`
+/// flag groups rather than by provider keywords/level
+pub mod kernel_providers {
+    use uuid::Uuid;
+
+    /// Well-known control GUID for the classic NT Kernel Logger session
+    pub const NT_KERNEL_LOGGER: Uuid = Uuid::from_u128(0x9e814aad_3204_11d2_9a82_006008a86939);
+}
+
 /// Well-known provider GUIDs
 pub mod known_providers {
     use uuid::Uuid;
@@ -259,6 +405,16 @@ impl PyEtwProvider {
         Ok(Self { inner: provider })
     }
 
+    /// Create a provider by friendly name (e.g.
+    /// `"Microsoft-Windows-PowerShell"`), looked up in the system's
+    /// registered-provider catalog
+    #[staticmethod]
+    fn from_name(name: &str) -> PyResult<Self> {
+        Ok(Self {
+            inner: EtwProvider::from_name(name)?,
+        })
+    }
+
     /// Create a provider for kernel process events
     #[staticmethod]
     fn kernel_process() -> Self {
@@ -286,6 +442,24 @@ impl PyEtwProvider {
         }
     }
 
+    /// Create an NT Kernel Logger provider capturing process events
+    /// (`EVENT_TRACE_FLAG_PROCESS`)
+    #[staticmethod]
+    fn kernel_process_flags() -> Self {
+        Self {
+            inner: EtwProvider::by_guid(kernel_providers::NT_KERNEL_LOGGER)
+                .with_name("NT Kernel Logger")
+                .with_kernel_flags(KernelFlag::Process.value()),
+        }
+    }
+
+    /// Set the NT Kernel Logger flag group this provider captures, ORing
+    /// together `KernelFlags.*` constants (see the `kernel` module)
+    fn kernel_flags(&mut self, flags: u32) -> Self {
+        self.inner.kernel_flags = flags;
+        self.clone()
+    }
+
     /// Provider GUID
     #[getter]
     fn guid(&self) -> String {
@@ -298,22 +472,60 @@ impl PyEtwProvider {
         self.inner.name.clone()
     }
 
-    /// Set trace level (0=Always to 5=Verbose)
-    fn level(&mut self, level: u8) -> Self {
-        self.inner.level = TraceLevel::from(level);
-        self.clone()
-    }
-
-    /// Set keywords (any match)
-    fn keywords_any(&mut self, keywords: u64) -> Self {
-        self.inner.keywords_any = keywords;
-        self.clone()
-    }
-
-    /// Set keywords (all must match)
-    fn keywords_all(&mut self, keywords: u64) -> Self {
-        self.inner.keywords_all = keywords;
-        self.clone()
+    /// Set trace level, either numerically (0=Always to 5=Verbose) or by
+    /// name (e.g. `"Error"`, `"Warning"`), resolved against the provider's
+    /// manifest via TDH
+    fn level(&mut self, level: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(level) = level.extract::<u8>() {
+            self.inner.level = TraceLevel::from(level);
+            return Ok(self.clone());
+        }
+        let name: String = level.extract().map_err(|_| {
+            pyo3::exceptions::PyTypeError::new_err("level must be an int or a level name")
+        })?;
+        self.inner.level = self.inner.resolve_level(&name)?;
+        Ok(self.clone())
+    }
+
+    /// Set keywords to match (any), either as a raw `u64` bitmask or as a
+    /// list of keyword names (e.g. `["Runspace", "Pipeline"]`) resolved
+    /// against the provider's manifest via TDH
+    fn keywords_any(&mut self, keywords: &Bound<'_, PyAny>) -> PyResult<Self> {
+        self.inner.keywords_any = keywords_arg_to_mask(&self.inner, keywords)?;
+        Ok(self.clone())
+    }
+
+    /// Set keywords that must all match, either as a raw `u64` bitmask or as
+    /// a list of keyword names resolved against the provider's manifest
+    fn keywords_all(&mut self, keywords: &Bound<'_, PyAny>) -> PyResult<Self> {
+        self.inner.keywords_all = keywords_arg_to_mask(&self.inner, keywords)?;
+        Ok(self.clone())
+    }
+
+    /// Resolve keyword names against this provider's manifest, OR-ing
+    /// together the resulting masks
+    fn resolve_keywords(&self, names: Vec<String>) -> PyResult<u64> {
+        let refs: Vec<&str> = names.iter().map(String::as_str).collect();
+        Ok(self.inner.resolve_keywords(&refs)?)
+    }
+
+    /// Register a declarative field layout for events from this provider
+    /// that TDH can't parse. `fields` is an ordered list of `(name, type)`
+    /// pairs, where `type` is one of `"u8"`, `"i8"`, `"u16"`, `"u32"`,
+    /// `"u64"`, `"i32"`, `"i64"`, `"f64"`, `"guid"`, `"sid"`, `"wstring"`,
+    /// `"wstring_prefixed"` or `"cstring"`.
+    fn register_schema(
+        &self,
+        event_id: u16,
+        version: u8,
+        fields: Vec<(String, String)>,
+    ) -> PyResult<()> {
+        let fields = fields
+            .into_iter()
+            .map(|(name, ty)| Ok((name, field_type_from_str(&ty)?)))
+            .collect::<PyResult<Vec<_>>>()?;
+        self.inner.register_schema(event_id, version, fields);
+        Ok(())
     }
 
     /// Filter by specific event IDs
@@ -328,9 +540,18 @@ impl PyEtwProvider {
         self.clone()
     }
 
-    /// Enable stack trace capture
+    /// Enable or disable stack trace capture, a convenience wrapper around
+    /// [`Self::enable_properties`] that sets/clears just the `STACK_TRACE` bit
     fn stack_trace(&mut self, enabled: bool) -> Self {
-        self.inner.capture_stack = enabled;
+        self.inner = self.inner.clone().with_stack_trace(enabled);
+        self.clone()
+    }
+
+    /// Set the full `EnableProperty` bitmask (e.g.
+    /// `EnableProperty.SID | EnableProperty.PROCESS_START_KEY`) passed to
+    /// `EnableTraceEx2` when this provider is enabled
+    fn enable_properties(&mut self, flags: u32) -> Self {
+        self.inner.enable_properties = flags;
         self.clone()
     }
 
@@ -390,4 +611,44 @@ mod tests {
         assert!(provider.matches_event(1, 0, 0, 0x08)); // Matches any
         assert!(!provider.matches_event(1, 0, 0, 0x10)); // No match
     }
+
+    #[test]
+    fn test_with_stack_trace_sets_and_clears_only_its_bit() {
+        let provider = EtwProvider::by_guid(Uuid::new_v4())
+            .with_enable_properties(EnableProperty::Sid.value())
+            .with_stack_trace(true);
+        assert_eq!(
+            provider.enable_properties,
+            EnableProperty::Sid.value() | EnableProperty::StackTrace.value()
+        );
+
+        let provider = provider.with_stack_trace(false);
+        assert_eq!(provider.enable_properties, EnableProperty::Sid.value());
+    }
+
+    #[test]
+    fn test_kernel_provider_matches_by_flag_not_keywords() {
+        let provider = EtwProvider::by_guid(kernel_providers::NT_KERNEL_LOGGER)
+            .with_kernel_flags(KernelFlag::Process.value() | KernelFlag::Thread.value())
+            .with_level(TraceLevel::Always); // level/keywords below are ignored for kernel providers
+
+        assert!(provider.matches_event(1, 0, 5, KernelFlag::Process.value()));
+        assert!(provider.matches_event(1, 0, 5, KernelFlag::Thread.value()));
+        assert!(!provider.matches_event(1, 0, 5, KernelFlag::Registry.value()));
+    }
+
+    #[test]
+    fn test_from_name_unknown_provider_errors() {
+        let result = EtwProvider::from_name("Definitely-Not-A-Registered-Provider");
+        assert!(matches!(result, Err(EtwError::ProviderNotFound(_))));
+    }
+
+    #[test]
+    fn test_resolve_keywords_unknown_name_errors() {
+        // No live Windows environment in this sandbox, so the manifest
+        // lookup itself always misses; this exercises the not-found path.
+        let provider = EtwProvider::by_guid(Uuid::new_v4());
+        let result = provider.resolve_keywords(&["DefinitelyNotARealKeyword"]);
+        assert!(matches!(result, Err(EtwError::KeywordNotFound(_))));
+    }
 }
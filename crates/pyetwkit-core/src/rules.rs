@@ -0,0 +1,577 @@
+//! Rule-based alerting over captured events
+//!
+//! Lets users declaratively flag events of interest instead of filtering in
+//! Python per-event: a [`Rule`] matches on provider/event id/level plus
+//! property predicates, and a [`RuleSet`] evaluates every rule against an
+//! event, producing [`Diagnostic`]s carrying the triggering rule's severity
+//! and a message filled in from the event's properties.
+
+use crate::event::{EtwEvent, EventValue};
+use crate::stats::SharedStatsTracker;
+
+use pyo3::prelude::*;
+use uuid::Uuid;
+
+/// How urgently a matched rule should be treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Severity {
+    Info = 0,
+    Warning = 1,
+    Error = 2,
+    Critical = 3,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+/// Comparison applied by a [`PropertyPredicate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PredicateOp {
+    Equals,
+    Contains,
+    GreaterThan,
+}
+
+/// A predicate matched against one decoded property of an event
+#[derive(Debug, Clone)]
+pub struct PropertyPredicate {
+    pub name: String,
+    pub op: PredicateOp,
+    pub value: EventValue,
+}
+
+impl PropertyPredicate {
+    pub fn new(name: impl Into<String>, op: PredicateOp, value: EventValue) -> Self {
+        Self {
+            name: name.into(),
+            op,
+            value,
+        }
+    }
+
+    pub(crate) fn matches(&self, event: &EtwEvent) -> bool {
+        let Some(actual) = event.properties.get(&self.name) else {
+            return false;
+        };
+        match self.op {
+            PredicateOp::Equals => values_equal(actual, &self.value),
+            PredicateOp::Contains => match (actual.as_string(), self.value.as_string()) {
+                (Some(a), Some(b)) => a.contains(&b),
+                _ => false,
+            },
+            PredicateOp::GreaterThan => match (numeric_value(actual), numeric_value(&self.value)) {
+                (Some(a), Some(b)) => a > b,
+                _ => false,
+            },
+        }
+    }
+}
+
+fn values_equal(a: &EventValue, b: &EventValue) -> bool {
+    if let (Some(x), Some(y)) = (numeric_value(a), numeric_value(b)) {
+        return x == y;
+    }
+    a.as_string() == b.as_string()
+}
+
+fn numeric_value(value: &EventValue) -> Option<f64> {
+    match value {
+        EventValue::I8(n) => Some(*n as f64),
+        EventValue::U8(n) => Some(*n as f64),
+        EventValue::I16(n) => Some(*n as f64),
+        EventValue::U16(n) => Some(*n as f64),
+        EventValue::I32(n) => Some(*n as f64),
+        EventValue::U32(n) => Some(*n as f64),
+        EventValue::I64(n) => Some(*n as f64),
+        EventValue::U64(n) => Some(*n as f64),
+        EventValue::F32(n) => Some(*n as f64),
+        EventValue::F64(n) => Some(*n),
+        EventValue::Pointer(p) => Some(*p as f64),
+        EventValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// A declarative alert condition
+#[derive(Debug, Clone)]
+pub struct Rule {
+    /// Human-readable rule name, surfaced on matching diagnostics
+    pub name: String,
+    /// Only match events from this provider GUID, if set
+    pub provider: Option<Uuid>,
+    /// Only match these event IDs, if set
+    pub event_ids: Option<Vec<u16>>,
+    /// Only match events at or more severe than this level (lower = more severe)
+    pub min_level: Option<u8>,
+    /// All of these property predicates must match
+    pub predicates: Vec<PropertyPredicate>,
+    /// Severity to report on match
+    pub severity: Severity,
+    /// Message template; `{PropertyName}` is replaced with the event's value
+    pub message_template: String,
+}
+
+impl Rule {
+    /// Create a new rule with no matcher conditions (matches everything)
+    pub fn new(name: impl Into<String>, severity: Severity) -> Self {
+        Self {
+            name: name.into(),
+            provider: None,
+            event_ids: None,
+            min_level: None,
+            predicates: Vec::new(),
+            severity,
+            message_template: String::new(),
+        }
+    }
+
+    pub fn with_provider(mut self, provider: Uuid) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    pub fn with_event_ids(mut self, ids: impl IntoIterator<Item = u16>) -> Self {
+        self.event_ids = Some(ids.into_iter().collect());
+        self
+    }
+
+    pub fn with_min_level(mut self, level: u8) -> Self {
+        self.min_level = Some(level);
+        self
+    }
+
+    pub fn with_predicate(mut self, predicate: PropertyPredicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    pub fn with_message(mut self, template: impl Into<String>) -> Self {
+        self.message_template = template.into();
+        self
+    }
+
+    /// Check whether this rule matches `event`
+    pub fn matches(&self, event: &EtwEvent) -> bool {
+        if let Some(provider) = self.provider {
+            if provider != event.provider_id {
+                return false;
+            }
+        }
+        if let Some(ids) = &self.event_ids {
+            if !ids.contains(&event.event_id) {
+                return false;
+            }
+        }
+        if let Some(min_level) = self.min_level {
+            if event.level > min_level {
+                return false;
+            }
+        }
+        self.predicates.iter().all(|p| p.matches(event))
+    }
+
+    /// Render `message_template`, substituting `{Name}` with the event's
+    /// decoded property values (or leaving the placeholder if missing).
+    fn render_message(&self, event: &EtwEvent) -> String {
+        let mut message = self.message_template.clone();
+        for (name, value) in &event.properties {
+            let placeholder = format!("{{{name}}}");
+            if message.contains(&placeholder) {
+                message = message.replace(&placeholder, &value.as_string().unwrap_or_default());
+            }
+        }
+        message
+    }
+}
+
+/// A rule match produced by [`RuleSet::evaluate`]
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub rule_name: String,
+    pub severity: Severity,
+    pub message: String,
+    pub event_id: u16,
+    pub provider_id: Uuid,
+}
+
+/// A collection of rules evaluated together against each event
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+    /// Match counts by severity, indexed by `Severity as usize`
+    match_counts: [u64; 4],
+    /// Stats tracker to mirror per-severity match counts into, if attached
+    /// via [`Self::with_stats`]
+    stats: Option<SharedStatsTracker>,
+}
+
+impl RuleSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule to the set
+    pub fn add(&mut self, rule: Rule) -> &mut Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Attach a session's stats tracker, so matches are also reflected in
+    /// its `SessionStats.rule_matches`, not just [`Self::match_counts`]
+    pub fn with_stats(mut self, stats: SharedStatsTracker) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Run every rule against `event`, returning one [`Diagnostic`] per match
+    /// and updating the per-severity match counters (and the attached stats
+    /// tracker, if any).
+    pub fn evaluate(&mut self, event: &EtwEvent) -> Vec<Diagnostic> {
+        let diagnostics: Vec<Diagnostic> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.matches(event))
+            .map(|rule| Diagnostic {
+                rule_name: rule.name.clone(),
+                severity: rule.severity,
+                message: rule.render_message(event),
+                event_id: event.event_id,
+                provider_id: event.provider_id,
+            })
+            .collect();
+
+        for diagnostic in &diagnostics {
+            self.match_counts[diagnostic.severity as usize] += 1;
+            if let Some(stats) = &self.stats {
+                stats.record_rule_match(diagnostic.severity.as_str());
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Match counts so far, keyed by severity name
+    pub fn match_counts(&self) -> std::collections::HashMap<String, u64> {
+        [
+            Severity::Info,
+            Severity::Warning,
+            Severity::Error,
+            Severity::Critical,
+        ]
+        .into_iter()
+        .map(|severity| (severity.as_str().to_string(), self.match_counts[severity as usize]))
+        .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+// Python bindings
+
+fn severity_from_str(value: &str) -> PyResult<Severity> {
+    match value.to_lowercase().as_str() {
+        "info" => Ok(Severity::Info),
+        "warning" => Ok(Severity::Warning),
+        "error" => Ok(Severity::Error),
+        "critical" => Ok(Severity::Critical),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unknown severity: {other}"
+        ))),
+    }
+}
+
+pub(crate) fn predicate_op_from_str(value: &str) -> PyResult<PredicateOp> {
+    match value.to_lowercase().as_str() {
+        "equals" | "eq" => Ok(PredicateOp::Equals),
+        "contains" => Ok(PredicateOp::Contains),
+        "gt" | "greater_than" => Ok(PredicateOp::GreaterThan),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unknown predicate op: {other}"
+        ))),
+    }
+}
+
+pub(crate) fn py_value_to_event_value(value: &Bound<'_, PyAny>) -> PyResult<EventValue> {
+    if let Ok(s) = value.extract::<String>() {
+        return Ok(EventValue::String(s));
+    }
+    if let Ok(n) = value.extract::<i64>() {
+        return Ok(EventValue::I64(n));
+    }
+    if let Ok(f) = value.extract::<f64>() {
+        return Ok(EventValue::F64(f));
+    }
+    if let Ok(b) = value.extract::<bool>() {
+        return Ok(EventValue::Bool(b));
+    }
+    Err(pyo3::exceptions::PyValueError::new_err(
+        "unsupported predicate value type",
+    ))
+}
+
+/// Python wrapper for a single alert rule
+#[pyclass(name = "Rule")]
+#[derive(Clone)]
+pub struct PyRule {
+    pub(crate) inner: Rule,
+}
+
+#[pymethods]
+impl PyRule {
+    /// Create a new rule. `severity` is one of "info"/"warning"/"error"/"critical"
+    #[new]
+    #[pyo3(signature = (name, severity="info"))]
+    fn new(name: &str, severity: &str) -> PyResult<Self> {
+        Ok(Self {
+            inner: Rule::new(name, severity_from_str(severity)?),
+        })
+    }
+
+    /// Restrict this rule to events from the given provider GUID
+    fn provider(&mut self, guid: &str) -> PyResult<Self> {
+        let uuid = Uuid::parse_str(guid)
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("Invalid GUID format"))?;
+        self.inner.provider = Some(uuid);
+        Ok(self.clone())
+    }
+
+    /// Restrict this rule to the given event IDs
+    fn event_ids(&mut self, ids: Vec<u16>) -> Self {
+        self.inner.event_ids = Some(ids);
+        self.clone()
+    }
+
+    /// Restrict this rule to events at or more severe than `level`
+    fn min_level(&mut self, level: u8) -> Self {
+        self.inner.min_level = Some(level);
+        self.clone()
+    }
+
+    /// Add a property predicate: `op` is one of "equals"/"contains"/"gt"
+    fn predicate(&mut self, py: Python<'_>, name: &str, op: &str, value: Py<PyAny>) -> PyResult<Self> {
+        let op = predicate_op_from_str(op)?;
+        let value = py_value_to_event_value(value.bind(py))?;
+        self.inner
+            .predicates
+            .push(PropertyPredicate::new(name, op, value));
+        Ok(self.clone())
+    }
+
+    /// Set the message template (`{PropertyName}` placeholders are substituted)
+    fn message(&mut self, template: &str) -> Self {
+        self.inner.message_template = template.to_string();
+        self.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Rule(name='{}', severity='{}')",
+            self.inner.name,
+            self.inner.severity.as_str()
+        )
+    }
+}
+
+/// Python wrapper for a rule match
+#[pyclass(name = "Diagnostic")]
+#[derive(Clone)]
+pub struct PyDiagnostic {
+    inner: Diagnostic,
+}
+
+#[pymethods]
+impl PyDiagnostic {
+    #[getter]
+    fn rule_name(&self) -> &str {
+        &self.inner.rule_name
+    }
+
+    #[getter]
+    fn severity(&self) -> &str {
+        self.inner.severity.as_str()
+    }
+
+    #[getter]
+    fn message(&self) -> &str {
+        &self.inner.message
+    }
+
+    #[getter]
+    fn event_id(&self) -> u16 {
+        self.inner.event_id
+    }
+
+    #[getter]
+    fn provider_id(&self) -> String {
+        self.inner.provider_id.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Diagnostic(rule='{}', severity='{}', message='{}')",
+            self.inner.rule_name,
+            self.inner.severity.as_str(),
+            self.inner.message
+        )
+    }
+}
+
+impl From<Diagnostic> for PyDiagnostic {
+    fn from(inner: Diagnostic) -> Self {
+        Self { inner }
+    }
+}
+
+/// Python wrapper for a collection of rules
+#[pyclass(name = "RuleSet")]
+#[derive(Clone, Default)]
+pub struct PyRuleSet {
+    inner: RuleSet,
+}
+
+#[pymethods]
+impl PyRuleSet {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule to the set
+    fn add(&mut self, rule: PyRule) {
+        self.inner.add(rule.inner);
+    }
+
+    /// Attach a session's stats tracker, so matches are also reflected in
+    /// `session.stats().rule_matches`
+    fn with_stats(&mut self, session: &crate::session::PyEtwSession) -> PyResult<()> {
+        let inner = std::mem::take(&mut self.inner);
+        self.inner = inner.with_stats(session.shared_stats()?);
+        Ok(())
+    }
+
+    /// Evaluate every rule against `event`, returning the matching diagnostics
+    fn evaluate(&mut self, event: &crate::event::PyEtwEvent) -> Vec<PyDiagnostic> {
+        self.inner
+            .evaluate(event.inner())
+            .into_iter()
+            .map(PyDiagnostic::from)
+            .collect()
+    }
+
+    /// Match counts so far, keyed by severity name
+    fn match_counts(&self) -> std::collections::HashMap<String, u64> {
+        self.inner.match_counts()
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("RuleSet(rules={})", self.inner.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_event() -> EtwEvent {
+        let mut event = EtwEvent::new(Uuid::new_v4(), 42);
+        event.level = 2;
+        event
+            .properties
+            .insert("Duration".to_string(), EventValue::U32(5000));
+        event
+    }
+
+    #[test]
+    fn test_rule_matches_event_id() {
+        let rule = Rule::new("slow-op", Severity::Warning).with_event_ids([42]);
+        assert!(rule.matches(&sample_event()));
+
+        let rule = Rule::new("slow-op", Severity::Warning).with_event_ids([1]);
+        assert!(!rule.matches(&sample_event()));
+    }
+
+    #[test]
+    fn test_rule_predicate_gt() {
+        let rule = Rule::new("slow-op", Severity::Critical).with_predicate(
+            PropertyPredicate::new("Duration", PredicateOp::GreaterThan, EventValue::U32(1000)),
+        );
+        assert!(rule.matches(&sample_event()));
+
+        let rule = Rule::new("slow-op", Severity::Critical).with_predicate(
+            PropertyPredicate::new("Duration", PredicateOp::GreaterThan, EventValue::U32(10000)),
+        );
+        assert!(!rule.matches(&sample_event()));
+    }
+
+    #[test]
+    fn test_ruleset_evaluate_and_message_template() {
+        let mut rules = RuleSet::new();
+        rules.add(
+            Rule::new("slow-op", Severity::Critical)
+                .with_event_ids([42])
+                .with_message("operation took {Duration}ms"),
+        );
+
+        let diagnostics = rules.evaluate(&sample_event());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Critical);
+        assert_eq!(diagnostics[0].message, "operation took 5000ms");
+    }
+
+    #[test]
+    fn test_ruleset_match_counts() {
+        let mut rules = RuleSet::new();
+        rules.add(Rule::new("slow-op", Severity::Critical).with_event_ids([42]));
+        rules.add(Rule::new("any-info", Severity::Info));
+
+        rules.evaluate(&sample_event());
+        rules.evaluate(&sample_event());
+
+        let counts = rules.match_counts();
+        assert_eq!(counts["critical"], 2);
+        assert_eq!(counts["info"], 2);
+        assert_eq!(counts["warning"], 0);
+    }
+
+    #[test]
+    fn test_ruleset_mirrors_matches_into_attached_stats() {
+        let stats = std::sync::Arc::new(crate::stats::StatsTracker::new(64, 64));
+        let mut rules = RuleSet::new().with_stats(stats.clone());
+        rules.add(Rule::new("slow-op", Severity::Critical).with_event_ids([42]));
+
+        rules.evaluate(&sample_event());
+        rules.evaluate(&sample_event());
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.rule_matches["critical"], 2);
+    }
+
+    #[test]
+    fn test_min_level_filters_less_severe_events() {
+        let rule = Rule::new("errors-only", Severity::Error).with_min_level(2);
+        assert!(rule.matches(&sample_event()));
+
+        let mut verbose_event = sample_event();
+        verbose_event.level = 5;
+        assert!(!rule.matches(&verbose_event));
+    }
+}
@@ -2,11 +2,21 @@
 //!
 //! This module provides schema resolution for ETW events using TDH APIs.
 
+use crate::codec::{write_bytes_with_varint_len, write_varint, Cursor};
+use crate::error::{EtwError, Result};
+use crate::event::{event_value_to_py, EventValue};
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use parking_lot::RwLock;
 use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use uuid::Uuid;
 
 /// Property type enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PropertyType {
     /// Null/empty value
     Null,
@@ -85,7 +95,7 @@ impl PropertyType {
 }
 
 /// Information about a single event property
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PropertyInfo {
     /// Property name
     pub name: String,
@@ -95,10 +105,18 @@ pub struct PropertyInfo {
     pub length: Option<u32>,
     /// Is this an array property
     pub is_array: bool,
+    /// Resolved enum/bitmap value map (TDH `MapNameOffset`), if this
+    /// property references one
+    pub value_map: Option<HashMap<u64, String>>,
+    /// Whether `value_map` is a bitmask (`EVENTMAP_INFO_FLAG_MANIFEST_BITMAP`)
+    /// rather than an enumerated value map
+    /// (`EVENTMAP_INFO_FLAG_MANIFEST_VALUEMAP`). Meaningless when
+    /// `value_map` is `None`.
+    pub is_bitmap: bool,
 }
 
 /// Event schema containing property definitions
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventSchema {
     /// Provider GUID
     pub provider_id: String,
@@ -112,10 +130,13 @@ pub struct EventSchema {
     pub properties: Vec<PropertyInfo>,
     /// Schema type (manifest, MOF, TraceLogging)
     pub schema_type: SchemaType,
+    /// Low-level TDH decoding source this schema was resolved from, e.g.
+    /// `DecodingSourceXMLFile` for manifest-based providers
+    pub decoding_source: DecodingSource,
 }
 
 /// Type of schema
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SchemaType {
     /// Manifest-based (XML)
     Manifest,
@@ -138,6 +159,33 @@ impl SchemaType {
     }
 }
 
+/// Low-level TDH decoding source, mirroring ferrisetw's `Schema::decoding_source`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecodingSource {
+    /// Manifest-based (XML) instrumentation
+    XmlFile,
+    /// MOF-based (WMI) instrumentation
+    Wbem,
+    /// WPP instrumentation
+    Wpp,
+    /// TraceLogging (self-describing) instrumentation
+    Tlg,
+    /// Unrecognized decoding source
+    Unknown,
+}
+
+impl DecodingSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DecodingSource::XmlFile => "DecodingSourceXMLFile",
+            DecodingSource::Wbem => "DecodingSourceWbem",
+            DecodingSource::Wpp => "DecodingSourceWPP",
+            DecodingSource::Tlg => "DecodingSourceTlg",
+            DecodingSource::Unknown => "DecodingSourceUnknown",
+        }
+    }
+}
+
 impl EventSchema {
     /// Get property names
     pub fn property_names(&self) -> Vec<&str> {
@@ -148,6 +196,210 @@ impl EventSchema {
     pub fn get_property(&self, name: &str) -> Option<&PropertyInfo> {
         self.properties.iter().find(|p| p.name == name)
     }
+
+    /// Decode the raw bytes of the named property into a native
+    /// [`EventValue`], per [`PropertyInfo::decode`]
+    pub fn decode_property(
+        &self,
+        name: &str,
+        raw: &[u8],
+        tz: TimestampTz,
+        fmt: Option<&str>,
+    ) -> Result<EventValue> {
+        let property = self
+            .get_property(name)
+            .ok_or_else(|| EtwError::DecodeError(format!("no such property: {name}")))?;
+        property.decode(raw, tz, fmt)
+    }
+}
+
+/// Timezone used when formatting a decoded `FileTime`/`SystemTime` as a
+/// string via `fmt`. Has no effect when `fmt` is `None`, since an
+/// unformatted timestamp is always carried as UTC (see
+/// [`EventValue::SystemTime`]), like [`crate::conversion::Conversion`]'s
+/// `TimestampFmt`/`TimestampTzFmt` split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampTz {
+    /// Format in UTC
+    Utc,
+    /// Format in the local system timezone
+    Local,
+}
+
+impl Default for TimestampTz {
+    fn default() -> Self {
+        TimestampTz::Utc
+    }
+}
+
+/// 1601-01-01 UTC to 1970-01-01 UTC, in 100ns intervals
+const FILETIME_UNIX_DIFF_100NS: i64 = 116_444_736_000_000_000;
+
+impl PropertyInfo {
+    /// Decode `raw` (a property's raw bytes from an event record) into a
+    /// native [`EventValue`], dispatching on [`PropertyType`]. `tz` and
+    /// `fmt` control how `FileTime`/`SystemTime` properties come out: with
+    /// `fmt`, they're rendered as a strftime-style string in `tz`;
+    /// without it, they come out as [`EventValue::SystemTime`] (always UTC).
+    pub fn decode(&self, raw: &[u8], tz: TimestampTz, fmt: Option<&str>) -> Result<EventValue> {
+        match self.property_type {
+            PropertyType::Null => Ok(EventValue::Null),
+            PropertyType::String => decode_wide_string(raw).map(EventValue::String),
+            PropertyType::Int8 => decode_fixed::<1>(raw).map(|b| EventValue::I8(b[0] as i8)),
+            PropertyType::UInt8 => decode_fixed::<1>(raw).map(|b| EventValue::U8(b[0])),
+            PropertyType::Int16 => {
+                decode_fixed::<2>(raw).map(|b| EventValue::I16(i16::from_le_bytes(b)))
+            }
+            PropertyType::UInt16 => {
+                decode_fixed::<2>(raw).map(|b| EventValue::U16(u16::from_le_bytes(b)))
+            }
+            PropertyType::Int32 => {
+                decode_fixed::<4>(raw).map(|b| EventValue::I32(i32::from_le_bytes(b)))
+            }
+            PropertyType::UInt32 => {
+                decode_fixed::<4>(raw).map(|b| EventValue::U32(u32::from_le_bytes(b)))
+            }
+            PropertyType::Int64 => {
+                decode_fixed::<8>(raw).map(|b| EventValue::I64(i64::from_le_bytes(b)))
+            }
+            PropertyType::UInt64 => {
+                decode_fixed::<8>(raw).map(|b| EventValue::U64(u64::from_le_bytes(b)))
+            }
+            PropertyType::Float => {
+                decode_fixed::<4>(raw).map(|b| EventValue::F32(f32::from_le_bytes(b)))
+            }
+            PropertyType::Double => {
+                decode_fixed::<8>(raw).map(|b| EventValue::F64(f64::from_le_bytes(b)))
+            }
+            PropertyType::Boolean => {
+                decode_fixed::<4>(raw).map(|b| EventValue::Bool(i32::from_le_bytes(b) != 0))
+            }
+            PropertyType::Binary => Ok(EventValue::Binary(raw.to_vec())),
+            PropertyType::Guid => decode_guid(raw).map(EventValue::Guid),
+            PropertyType::Pointer => {
+                decode_fixed::<8>(raw).map(|b| EventValue::Pointer(u64::from_le_bytes(b)))
+            }
+            PropertyType::FileTime => {
+                let ft = i64::from_le_bytes(decode_fixed::<8>(raw)?);
+                decode_filetime(ft, tz, fmt)
+            }
+            PropertyType::SystemTime => decode_systemtime(raw, tz, fmt),
+            PropertyType::Sid => decode_sid(raw).map(EventValue::Sid),
+            PropertyType::HexInt32 => {
+                decode_fixed::<4>(raw).map(|b| EventValue::U32(u32::from_le_bytes(b)))
+            }
+            PropertyType::HexInt64 => {
+                decode_fixed::<8>(raw).map(|b| EventValue::U64(u64::from_le_bytes(b)))
+            }
+            PropertyType::Unknown => Ok(EventValue::Binary(raw.to_vec())),
+        }
+    }
+}
+
+fn decode_fixed<const N: usize>(raw: &[u8]) -> Result<[u8; N]> {
+    raw.try_into().map_err(|_| {
+        EtwError::DecodeError(format!("expected {N} bytes for this property, got {}", raw.len()))
+    })
+}
+
+fn decode_wide_string(raw: &[u8]) -> Result<String> {
+    if raw.len() % 2 != 0 {
+        return Err(EtwError::DecodeError(
+            "string property has an odd number of bytes".to_string(),
+        ));
+    }
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&unit| unit != 0)
+        .collect();
+    Ok(String::from_utf16_lossy(&units))
+}
+
+fn decode_guid(raw: &[u8]) -> Result<Uuid> {
+    let bytes = decode_fixed::<16>(raw)?;
+    let data1 = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+    let data2 = u16::from_le_bytes(bytes[4..6].try_into().unwrap());
+    let data3 = u16::from_le_bytes(bytes[6..8].try_into().unwrap());
+    let data4: [u8; 8] = bytes[8..16].try_into().unwrap();
+    Ok(Uuid::from_fields(data1, data2, data3, &data4))
+}
+
+fn decode_sid(raw: &[u8]) -> Result<String> {
+    if raw.len() < 8 {
+        return Err(EtwError::DecodeError("SID is too short".to_string()));
+    }
+    let revision = raw[0];
+    let sub_authority_count = raw[1] as usize;
+    let authority = raw[2..8]
+        .iter()
+        .fold(0u64, |acc, &byte| (acc << 8) | byte as u64);
+    let expected_len = 8 + sub_authority_count * 4;
+    if raw.len() < expected_len {
+        return Err(EtwError::DecodeError(format!(
+            "SID is truncated: expected {expected_len} bytes, got {}",
+            raw.len()
+        )));
+    }
+    let mut sid = format!("S-{revision}-{authority}");
+    for i in 0..sub_authority_count {
+        let offset = 8 + i * 4;
+        let sub_authority = u32::from_le_bytes(raw[offset..offset + 4].try_into().unwrap());
+        sid.push('-');
+        sid.push_str(&sub_authority.to_string());
+    }
+    Ok(sid)
+}
+
+fn decode_filetime(ft: i64, tz: TimestampTz, fmt: Option<&str>) -> Result<EventValue> {
+    let unix_100ns = ft - FILETIME_UNIX_DIFF_100NS;
+    let secs = unix_100ns.div_euclid(10_000_000);
+    let nanos = (unix_100ns.rem_euclid(10_000_000) * 100) as u32;
+    let timestamp = Utc
+        .timestamp_opt(secs, nanos)
+        .single()
+        .ok_or_else(|| EtwError::DecodeError(format!("out-of-range FileTime: {ft}")))?;
+    Ok(format_timestamp(timestamp, tz, fmt))
+}
+
+fn decode_systemtime(raw: &[u8], tz: TimestampTz, fmt: Option<&str>) -> Result<EventValue> {
+    let bytes = decode_fixed::<16>(raw)?;
+    let field = |i: usize| u16::from_le_bytes([bytes[i * 2], bytes[i * 2 + 1]]);
+    let (year, month, day) = (field(0), field(1), field(3));
+    let (hour, minute, second, millisecond) = (field(4), field(5), field(6), field(7));
+    let timestamp = chrono::NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)
+        .and_then(|date| {
+            date.and_hms_milli_opt(hour as u32, minute as u32, second as u32, millisecond as u32)
+        })
+        .map(|naive| Utc.from_utc_datetime(&naive))
+        .ok_or_else(|| {
+            EtwError::DecodeError(format!(
+                "invalid SYSTEMTIME: {year}-{month}-{day} {hour}:{minute}:{second}.{millisecond}"
+            ))
+        })?;
+    Ok(format_timestamp(timestamp, tz, fmt))
+}
+
+fn format_timestamp(timestamp: DateTime<Utc>, tz: TimestampTz, fmt: Option<&str>) -> EventValue {
+    match fmt {
+        None => EventValue::SystemTime(timestamp),
+        Some(fmt) => match tz {
+            TimestampTz::Utc => EventValue::String(timestamp.format(fmt).to_string()),
+            TimestampTz::Local => {
+                EventValue::String(timestamp.with_timezone(&Local).format(fmt).to_string())
+            }
+        },
+    }
+}
+
+pub(crate) fn timestamp_tz_from_str(value: &str) -> PyResult<TimestampTz> {
+    match value.to_lowercase().as_str() {
+        "utc" => Ok(TimestampTz::Utc),
+        "local" => Ok(TimestampTz::Local),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unknown timezone: {other} (expected \"utc\" or \"local\")"
+        ))),
+    }
 }
 
 /// Schema cache for efficient lookups
@@ -191,6 +443,297 @@ impl Default for SchemaCache {
     }
 }
 
+const SCHEMA_CACHE_MAGIC: &[u8; 4] = b"PESC";
+const SCHEMA_CACHE_VERSION: u8 = 1;
+const SCHEMA_CACHE_FORMAT_BINARY: u8 = 0;
+const SCHEMA_CACHE_FORMAT_JSON: u8 = 1;
+
+impl SchemaCache {
+    /// Serialize this cache to the compact binary format (see [`crate::codec`])
+    /// and write it to `path`
+    pub fn dump_binary<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut out = Vec::new();
+        out.extend_from_slice(SCHEMA_CACHE_MAGIC);
+        out.push(SCHEMA_CACHE_FORMAT_BINARY);
+        out.push(SCHEMA_CACHE_VERSION);
+        write_varint(&mut out, self.cache.len() as u64);
+        for schema in self.cache.values() {
+            encode_schema(&mut out, schema);
+        }
+        std::fs::write(path, out)?;
+        Ok(())
+    }
+
+    /// Serialize this cache to human-readable JSON and write it to `path`
+    pub fn dump_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let schemas: Vec<&EventSchema> = self.cache.values().collect();
+        let json = serde_json::to_string_pretty(&schemas)
+            .map_err(|e| EtwError::DecodeError(e.to_string()))?;
+
+        let mut out = Vec::new();
+        out.extend_from_slice(SCHEMA_CACHE_MAGIC);
+        out.push(SCHEMA_CACHE_FORMAT_JSON);
+        out.push(SCHEMA_CACHE_VERSION);
+        out.extend_from_slice(json.as_bytes());
+        std::fs::write(path, out)?;
+        Ok(())
+    }
+
+    /// Load a cache previously written by [`SchemaCache::dump_binary`] or
+    /// [`SchemaCache::dump_json`], auto-detecting the format from its header
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(EtwError::FileNotFound(path.to_string_lossy().to_string()));
+        }
+        let bytes = std::fs::read(path)?;
+        if bytes.len() < 6 || bytes[0..4] != *SCHEMA_CACHE_MAGIC {
+            return Err(EtwError::DecodeError(
+                "not a PyETWkit schema cache file".to_string(),
+            ));
+        }
+        let format = bytes[4];
+        let version = bytes[5];
+        if version != SCHEMA_CACHE_VERSION {
+            return Err(EtwError::DecodeError(format!(
+                "unsupported schema cache version: {version}"
+            )));
+        }
+
+        let payload = &bytes[6..];
+        let mut cache = SchemaCache::new();
+        match format {
+            SCHEMA_CACHE_FORMAT_BINARY => {
+                let mut cur = Cursor::new(payload);
+                let count = cur.varint()? as usize;
+                for _ in 0..count {
+                    cache.insert(decode_schema(&mut cur)?);
+                }
+            }
+            SCHEMA_CACHE_FORMAT_JSON => {
+                let text = std::str::from_utf8(payload)
+                    .map_err(|e| EtwError::DecodeError(e.to_string()))?;
+                let schemas: Vec<EventSchema> = serde_json::from_str(text)
+                    .map_err(|e| EtwError::DecodeError(e.to_string()))?;
+                for schema in schemas {
+                    cache.insert(schema);
+                }
+            }
+            other => {
+                return Err(EtwError::DecodeError(format!(
+                    "unknown schema cache format: {other}"
+                )))
+            }
+        }
+        Ok(cache)
+    }
+}
+
+fn encode_schema(out: &mut Vec<u8>, schema: &EventSchema) {
+    write_bytes_with_varint_len(out, schema.provider_id.as_bytes());
+    out.extend_from_slice(&schema.event_id.to_le_bytes());
+    out.push(schema.version);
+    match &schema.event_name {
+        Some(name) => {
+            out.push(1);
+            write_bytes_with_varint_len(out, name.as_bytes());
+        }
+        None => out.push(0),
+    }
+    write_varint(out, schema.properties.len() as u64);
+    for property in &schema.properties {
+        encode_property(out, property);
+    }
+    out.push(schema_type_tag(schema.schema_type));
+    out.push(decoding_source_tag(schema.decoding_source));
+}
+
+fn decode_schema(cur: &mut Cursor) -> Result<EventSchema> {
+    let provider_id = String::from_utf8(cur.bytes_with_varint_len()?.to_vec())
+        .map_err(|e| EtwError::DecodeError(e.to_string()))?;
+    let event_id = cur.u16()?;
+    let version = cur.u8()?;
+    let event_name = if cur.u8()? != 0 {
+        Some(
+            String::from_utf8(cur.bytes_with_varint_len()?.to_vec())
+                .map_err(|e| EtwError::DecodeError(e.to_string()))?,
+        )
+    } else {
+        None
+    };
+    let property_count = cur.varint()? as usize;
+    let mut properties = Vec::with_capacity(property_count.min(4096));
+    for _ in 0..property_count {
+        properties.push(decode_property(cur)?);
+    }
+    let schema_type = schema_type_from_tag(cur.u8()?);
+    let decoding_source = decoding_source_from_tag(cur.u8()?);
+    Ok(EventSchema {
+        provider_id,
+        event_id,
+        version,
+        event_name,
+        properties,
+        schema_type,
+        decoding_source,
+    })
+}
+
+fn encode_property(out: &mut Vec<u8>, property: &PropertyInfo) {
+    write_bytes_with_varint_len(out, property.name.as_bytes());
+    out.push(property_type_tag(property.property_type));
+    match property.length {
+        Some(length) => {
+            out.push(1);
+            out.extend_from_slice(&length.to_le_bytes());
+        }
+        None => out.push(0),
+    }
+    out.push(property.is_array as u8);
+    match &property.value_map {
+        Some(map) => {
+            out.push(1);
+            write_varint(out, map.len() as u64);
+            for (key, value) in map {
+                out.extend_from_slice(&key.to_le_bytes());
+                write_bytes_with_varint_len(out, value.as_bytes());
+            }
+        }
+        None => out.push(0),
+    }
+    out.push(property.is_bitmap as u8);
+}
+
+fn decode_property(cur: &mut Cursor) -> Result<PropertyInfo> {
+    let name = String::from_utf8(cur.bytes_with_varint_len()?.to_vec())
+        .map_err(|e| EtwError::DecodeError(e.to_string()))?;
+    let property_type = property_type_from_tag(cur.u8()?);
+    let length = if cur.u8()? != 0 { Some(cur.u32()?) } else { None };
+    let is_array = cur.u8()? != 0;
+    let value_map = if cur.u8()? != 0 {
+        let count = cur.varint()? as usize;
+        let mut map = HashMap::with_capacity(count.min(4096));
+        for _ in 0..count {
+            let key = cur.u64()?;
+            let value = String::from_utf8(cur.bytes_with_varint_len()?.to_vec())
+                .map_err(|e| EtwError::DecodeError(e.to_string()))?;
+            map.insert(key, value);
+        }
+        Some(map)
+    } else {
+        None
+    };
+    let is_bitmap = cur.u8()? != 0;
+    Ok(PropertyInfo {
+        name,
+        property_type,
+        length,
+        is_array,
+        value_map,
+        is_bitmap,
+    })
+}
+
+fn property_type_tag(property_type: PropertyType) -> u8 {
+    match property_type {
+        PropertyType::Null => 0,
+        PropertyType::String => 1,
+        PropertyType::Int8 => 2,
+        PropertyType::UInt8 => 3,
+        PropertyType::Int16 => 4,
+        PropertyType::UInt16 => 5,
+        PropertyType::Int32 => 6,
+        PropertyType::UInt32 => 7,
+        PropertyType::Int64 => 8,
+        PropertyType::UInt64 => 9,
+        PropertyType::Float => 10,
+        PropertyType::Double => 11,
+        PropertyType::Boolean => 12,
+        PropertyType::Binary => 13,
+        PropertyType::Guid => 14,
+        PropertyType::Pointer => 15,
+        PropertyType::FileTime => 16,
+        PropertyType::SystemTime => 17,
+        PropertyType::Sid => 18,
+        PropertyType::HexInt32 => 19,
+        PropertyType::HexInt64 => 20,
+        PropertyType::Unknown => 21,
+    }
+}
+
+/// Map a tag written by [`property_type_tag`] back to a [`PropertyType`],
+/// falling back to `Unknown` for tags from a newer format this build
+/// doesn't recognize, so a cache file stays loadable across versions.
+fn property_type_from_tag(tag: u8) -> PropertyType {
+    match tag {
+        0 => PropertyType::Null,
+        1 => PropertyType::String,
+        2 => PropertyType::Int8,
+        3 => PropertyType::UInt8,
+        4 => PropertyType::Int16,
+        5 => PropertyType::UInt16,
+        6 => PropertyType::Int32,
+        7 => PropertyType::UInt32,
+        8 => PropertyType::Int64,
+        9 => PropertyType::UInt64,
+        10 => PropertyType::Float,
+        11 => PropertyType::Double,
+        12 => PropertyType::Boolean,
+        13 => PropertyType::Binary,
+        14 => PropertyType::Guid,
+        15 => PropertyType::Pointer,
+        16 => PropertyType::FileTime,
+        17 => PropertyType::SystemTime,
+        18 => PropertyType::Sid,
+        19 => PropertyType::HexInt32,
+        20 => PropertyType::HexInt64,
+        _ => PropertyType::Unknown,
+    }
+}
+
+fn schema_type_tag(schema_type: SchemaType) -> u8 {
+    match schema_type {
+        SchemaType::Manifest => 0,
+        SchemaType::Mof => 1,
+        SchemaType::TraceLogging => 2,
+        SchemaType::Unknown => 3,
+    }
+}
+
+fn schema_type_from_tag(tag: u8) -> SchemaType {
+    match tag {
+        0 => SchemaType::Manifest,
+        1 => SchemaType::Mof,
+        2 => SchemaType::TraceLogging,
+        _ => SchemaType::Unknown,
+    }
+}
+
+fn decoding_source_tag(decoding_source: DecodingSource) -> u8 {
+    match decoding_source {
+        DecodingSource::XmlFile => 0,
+        DecodingSource::Wbem => 1,
+        DecodingSource::Wpp => 2,
+        DecodingSource::Tlg => 3,
+        DecodingSource::Unknown => 4,
+    }
+}
+
+fn decoding_source_from_tag(tag: u8) -> DecodingSource {
+    match tag {
+        0 => DecodingSource::XmlFile,
+        1 => DecodingSource::Wbem,
+        2 => DecodingSource::Wpp,
+        3 => DecodingSource::Tlg,
+        _ => DecodingSource::Unknown,
+    }
+}
+
+/// Shared cache handle, passed into session processing threads so a schema
+/// resolver can populate entries that event parsing then looks up by
+/// `(provider_id, event_id, version)`
+pub type SharedSchemaCache = Arc<RwLock<SchemaCache>>;
+
 // Python bindings
 
 /// Python wrapper for PropertyInfo
@@ -226,6 +769,18 @@ impl PyPropertyInfo {
         self.inner.is_array
     }
 
+    /// Resolved enum/bitmap value map, if this property references one
+    #[getter]
+    fn value_map(&self) -> Option<HashMap<u64, String>> {
+        self.inner.value_map.clone()
+    }
+
+    /// Whether `value_map` is a bitmask rather than an enumerated value map
+    #[getter]
+    fn is_bitmap(&self) -> bool {
+        self.inner.is_bitmap
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "PropertyInfo(name='{}', type='{}', is_array={})",
@@ -281,6 +836,12 @@ impl PyEventSchema {
         self.inner.schema_type.as_str()
     }
 
+    /// Low-level TDH decoding source, e.g. `"DecodingSourceXMLFile"`
+    #[getter]
+    fn decoding_source(&self) -> &str {
+        self.inner.decoding_source.as_str()
+    }
+
     /// Get property names
     fn property_names(&self) -> Vec<String> {
         self.inner
@@ -309,6 +870,27 @@ impl PyEventSchema {
             .map(PyPropertyInfo::from)
     }
 
+    /// Decode the raw bytes of a property (by name) into a native Python
+    /// value, per its TDH property type: integers/floats become `int`/
+    /// `float`, `Boolean` becomes `bool`, `Guid`/`Sid` become canonical
+    /// strings, `Binary` becomes `bytes`. `FileTime`/`SystemTime` become a
+    /// timestamp unless `fmt` (a strftime-style format string) is given, in
+    /// which case they're rendered as a string in `tz` (`"utc"` or
+    /// `"local"`, default `"utc"`).
+    #[pyo3(signature = (name, raw, *, tz="utc", fmt=None))]
+    fn decode_property(
+        &self,
+        py: Python<'_>,
+        name: &str,
+        raw: &[u8],
+        tz: &str,
+        fmt: Option<&str>,
+    ) -> PyResult<PyObject> {
+        let tz = timestamp_tz_from_str(tz)?;
+        let value = self.inner.decode_property(name, raw, tz, fmt)?;
+        event_value_to_py(py, &value)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "EventSchema(provider='{}', event_id={}, properties={})",
@@ -350,6 +932,25 @@ impl PySchemaCache {
         self.inner.len()
     }
 
+    /// Serialize this cache to the compact binary format and write it to `path`
+    fn dump_binary(&self, path: &str) -> PyResult<()> {
+        Ok(self.inner.dump_binary(path)?)
+    }
+
+    /// Serialize this cache to human-readable JSON and write it to `path`
+    fn dump_json(&self, path: &str) -> PyResult<()> {
+        Ok(self.inner.dump_json(path)?)
+    }
+
+    /// Load a cache previously written by `dump_binary` or `dump_json`,
+    /// auto-detecting the format from its header
+    #[staticmethod]
+    fn load(path: &str) -> PyResult<Self> {
+        Ok(Self {
+            inner: SchemaCache::load(path)?,
+        })
+    }
+
     fn __repr__(&self) -> String {
         format!("SchemaCache(entries={})", self.inner.len())
     }
@@ -366,6 +967,13 @@ mod tests {
         assert_eq!(PropertyType::Guid.as_str(), "guid");
     }
 
+    #[test]
+    fn test_decoding_source_as_str() {
+        assert_eq!(DecodingSource::XmlFile.as_str(), "DecodingSourceXMLFile");
+        assert_eq!(DecodingSource::Wbem.as_str(), "DecodingSourceWbem");
+        assert_eq!(DecodingSource::Tlg.as_str(), "DecodingSourceTlg");
+    }
+
     #[test]
     fn test_schema_property_names() {
         let schema = EventSchema {
@@ -379,18 +987,174 @@ mod tests {
                     property_type: PropertyType::UInt32,
                     length: Some(4),
                     is_array: false,
+                    value_map: None,
+                    is_bitmap: false,
                 },
                 PropertyInfo {
                     name: "ImageFileName".to_string(),
                     property_type: PropertyType::String,
                     length: None,
                     is_array: false,
+                    value_map: None,
+                    is_bitmap: false,
                 },
             ],
             schema_type: SchemaType::Manifest,
+            decoding_source: DecodingSource::XmlFile,
         };
 
         let names = schema.property_names();
         assert_eq!(names, vec!["ProcessId", "ImageFileName"]);
     }
+
+    fn property(property_type: PropertyType) -> PropertyInfo {
+        PropertyInfo {
+            name: "Prop".to_string(),
+            property_type,
+            length: None,
+            is_array: false,
+            value_map: None,
+            is_bitmap: false,
+        }
+    }
+
+    #[test]
+    fn test_decode_uint32() {
+        let prop = property(PropertyType::UInt32);
+        let value = prop.decode(&42u32.to_le_bytes(), TimestampTz::Utc, None).unwrap();
+        assert!(matches!(value, EventValue::U32(42)));
+    }
+
+    #[test]
+    fn test_decode_wide_string() {
+        let prop = property(PropertyType::String);
+        let raw: Vec<u8> = "hi".encode_utf16().flat_map(|u| u.to_le_bytes()).chain([0, 0]).collect();
+        let value = prop.decode(&raw, TimestampTz::Utc, None).unwrap();
+        assert!(matches!(value, EventValue::String(s) if s == "hi"));
+    }
+
+    #[test]
+    fn test_decode_wrong_length_errors() {
+        let prop = property(PropertyType::UInt32);
+        assert!(prop.decode(&[1, 2], TimestampTz::Utc, None).is_err());
+    }
+
+    #[test]
+    fn test_decode_sid() {
+        let prop = property(PropertyType::Sid);
+        // revision=1, sub_authority_count=2, authority=5 (NT authority), sub-authorities 21, 1
+        let mut raw = vec![1u8, 2, 0, 0, 0, 0, 0, 5];
+        raw.extend_from_slice(&21u32.to_le_bytes());
+        raw.extend_from_slice(&1u32.to_le_bytes());
+        let value = prop.decode(&raw, TimestampTz::Utc, None).unwrap();
+        assert!(matches!(value, EventValue::Sid(s) if s == "S-1-5-21-1"));
+    }
+
+    #[test]
+    fn test_decode_filetime_as_timestamp() {
+        let prop = property(PropertyType::FileTime);
+        // 2021-01-01 00:00:00 UTC
+        let ft: i64 = 132_532_416_000_000_000;
+        let value = prop.decode(&ft.to_le_bytes(), TimestampTz::Utc, None).unwrap();
+        match value {
+            EventValue::SystemTime(dt) => assert_eq!(dt.to_rfc3339(), "2021-01-01T00:00:00+00:00"),
+            other => panic!("unexpected value: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_filetime_with_fmt() {
+        let prop = property(PropertyType::FileTime);
+        let ft: i64 = 132_532_416_000_000_000;
+        let value = prop
+            .decode(&ft.to_le_bytes(), TimestampTz::Utc, Some("%Y-%m-%d"))
+            .unwrap();
+        assert!(matches!(value, EventValue::String(s) if s == "2021-01-01"));
+    }
+
+    #[test]
+    fn test_timestamp_tz_from_str() {
+        assert_eq!(timestamp_tz_from_str("utc").unwrap(), TimestampTz::Utc);
+        assert_eq!(timestamp_tz_from_str("local").unwrap(), TimestampTz::Local);
+        assert!(timestamp_tz_from_str("nonsense").is_err());
+    }
+
+    fn sample_cache() -> SchemaCache {
+        let mut cache = SchemaCache::new();
+        cache.insert(EventSchema {
+            provider_id: "test-provider".to_string(),
+            event_id: 1,
+            version: 0,
+            event_name: Some("TestEvent".to_string()),
+            properties: vec![
+                PropertyInfo {
+                    name: "ProcessId".to_string(),
+                    property_type: PropertyType::UInt32,
+                    length: Some(4),
+                    is_array: false,
+                    value_map: None,
+                    is_bitmap: false,
+                },
+                PropertyInfo {
+                    name: "Flags".to_string(),
+                    property_type: PropertyType::UInt32,
+                    length: None,
+                    is_array: false,
+                    value_map: Some(HashMap::from([(1, "Read".to_string())])),
+                    is_bitmap: true,
+                },
+            ],
+            schema_type: SchemaType::Manifest,
+            decoding_source: DecodingSource::XmlFile,
+        });
+        cache
+    }
+
+    #[test]
+    fn test_dump_load_binary_round_trip() {
+        let dir = std::env::temp_dir().join(format!("pyetwkit-schema-cache-{:?}", std::thread::current().id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cache.bin");
+
+        let cache = sample_cache();
+        cache.dump_binary(&path).unwrap();
+        let loaded = SchemaCache::load(&path).unwrap();
+
+        assert_eq!(loaded.len(), cache.len());
+        let schema = loaded.get("test-provider", 1, 0).unwrap();
+        assert_eq!(schema.event_name.as_deref(), Some("TestEvent"));
+        assert_eq!(schema.properties.len(), 2);
+        assert_eq!(schema.properties[1].value_map.as_ref().unwrap()[&1], "Read");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_dump_load_json_round_trip() {
+        let dir = std::env::temp_dir().join(format!("pyetwkit-schema-cache-json-{:?}", std::thread::current().id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cache.json");
+
+        let cache = sample_cache();
+        cache.dump_json(&path).unwrap();
+        let loaded = SchemaCache::load(&path).unwrap();
+
+        assert_eq!(loaded.len(), cache.len());
+        let schema = loaded.get("test-provider", 1, 0).unwrap();
+        assert_eq!(schema.schema_type, SchemaType::Manifest);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_missing_file_errors() {
+        let result = SchemaCache::load("/nonexistent/path/to/cache.bin");
+        assert!(matches!(result, Err(EtwError::FileNotFound(_))));
+    }
+
+    #[test]
+    fn test_property_type_from_tag_falls_back_to_unknown() {
+        assert_eq!(property_type_from_tag(255), PropertyType::Unknown);
+        assert_eq!(property_type_from_tag(property_type_tag(PropertyType::Guid)), PropertyType::Guid);
+    }
 }
@@ -0,0 +1,308 @@
+//! Declarative custom event-schema registry
+//!
+//! TDH can't parse events from providers with no manifest (or a manifest TDH
+//! just doesn't recognize), which otherwise surfaces as
+//! [`EtwError::SchemaNotFound`]. This registry lets analysts describe such
+//! an event's field layout up front, keyed by `(provider_guid, event_id,
+//! version)`, so [`decode`] can walk the raw user-data buffer field-by-field
+//! into the same `HashMap<String, EventValue>` shape manifest-based events
+//! produce.
+
+use crate::error::{EtwError, Result};
+use crate::event::EventValue;
+
+use parking_lot::RwLock;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// How a [`FieldType::WString`] field's length is determined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WStringEncoding {
+    /// Scan for a UTF-16 NUL (`0x0000`) terminator
+    NullTerminated,
+    /// A `u16` character count precedes the UTF-16 data
+    LengthPrefixed,
+}
+
+/// A registered custom field's wire layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    U8,
+    I8,
+    U16,
+    U32,
+    U64,
+    I32,
+    I64,
+    F64,
+    /// 16-byte binary GUID
+    Guid,
+    /// Binary SID: revision byte, sub-authority count byte, 6-byte
+    /// big-endian authority, then that many little-endian `u32`
+    /// sub-authorities
+    Sid,
+    /// UTF-16LE string
+    WString(WStringEncoding),
+    /// NUL-terminated 8-bit string
+    CString,
+}
+
+type SchemaKey = (Uuid, u16, u8);
+
+fn registry() -> &'static RwLock<HashMap<SchemaKey, Vec<(String, FieldType)>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<SchemaKey, Vec<(String, FieldType)>>>> =
+        OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Register a declarative field layout for `(guid, event_id, version)`, to
+/// be consulted by [`decode`] when TDH has no manifest for that event
+pub fn register_schema(guid: Uuid, event_id: u16, version: u8, fields: Vec<(String, FieldType)>) {
+    registry().write().insert((guid, event_id, version), fields);
+}
+
+/// Decode `raw` (an event's user-data buffer) against the field layout
+/// registered for `(guid, event_id, version)`. Returns `None` if no layout
+/// is registered for that key, so callers can fall back to their usual
+/// `EtwError::SchemaNotFound` handling.
+pub fn decode(
+    guid: &Uuid,
+    event_id: u16,
+    version: u8,
+    raw: &[u8],
+) -> Option<Result<HashMap<String, EventValue>>> {
+    let fields = registry().read().get(&(*guid, event_id, version))?.clone();
+    Some(decode_fields(&fields, raw))
+}
+
+fn decode_fields(
+    fields: &[(String, FieldType)],
+    raw: &[u8],
+) -> Result<HashMap<String, EventValue>> {
+    let mut cursor = FieldCursor::new(raw);
+    let mut values = HashMap::with_capacity(fields.len());
+    for (name, field_type) in fields {
+        values.insert(name.clone(), decode_field(&mut cursor, *field_type)?);
+    }
+    Ok(values)
+}
+
+fn decode_field(cursor: &mut FieldCursor, field_type: FieldType) -> Result<EventValue> {
+    Ok(match field_type {
+        FieldType::U8 => EventValue::U8(cursor.advance_u8()?),
+        FieldType::I8 => EventValue::I8(cursor.advance_u8()? as i8),
+        FieldType::U16 => EventValue::U16(cursor.advance_u16()?),
+        FieldType::U32 => EventValue::U32(cursor.advance_u32()?),
+        FieldType::U64 => EventValue::U64(cursor.advance_u64()?),
+        FieldType::I32 => EventValue::I32(cursor.advance_u32()? as i32),
+        FieldType::I64 => EventValue::I64(cursor.advance_u64()? as i64),
+        FieldType::F64 => EventValue::F64(f64::from_bits(cursor.advance_u64()?)),
+        FieldType::Guid => EventValue::Guid(cursor.advance_guid()?),
+        FieldType::Sid => EventValue::Sid(cursor.advance_sid()?),
+        FieldType::WString(WStringEncoding::NullTerminated) => {
+            EventValue::String(cursor.advance_wstring_nul()?)
+        }
+        FieldType::WString(WStringEncoding::LengthPrefixed) => {
+            EventValue::String(cursor.advance_wstring_prefixed()?)
+        }
+        FieldType::CString => EventValue::String(cursor.advance_cstring()?),
+    })
+}
+
+/// Bounds-checked cursor over a custom event's raw user-data buffer
+struct FieldCursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> FieldCursor<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| {
+                EtwError::DecodeError("unexpected end of custom event data".to_string())
+            })?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn advance_u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn advance_u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn advance_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn advance_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn advance_guid(&mut self) -> Result<Uuid> {
+        let raw = self.take(16)?;
+        let data1 = u32::from_le_bytes(raw[0..4].try_into().unwrap());
+        let data2 = u16::from_le_bytes(raw[4..6].try_into().unwrap());
+        let data3 = u16::from_le_bytes(raw[6..8].try_into().unwrap());
+        let data4: [u8; 8] = raw[8..16].try_into().unwrap();
+        Ok(Uuid::from_fields(data1, data2, data3, &data4))
+    }
+
+    fn advance_sid(&mut self) -> Result<String> {
+        let header = self.take(2)?;
+        let revision = header[0];
+        let sub_count = header[1] as usize;
+        let authority = self.take(6)?;
+        let authority_value = authority
+            .iter()
+            .fold(0u64, |acc, &byte| (acc << 8) | byte as u64);
+
+        let mut sid = format!("S-{revision}-{authority_value}");
+        for _ in 0..sub_count {
+            sid.push_str(&format!("-{}", self.advance_u32()?));
+        }
+        Ok(sid)
+    }
+
+    fn advance_wstring_nul(&mut self) -> Result<String> {
+        let start = self.pos;
+        loop {
+            if self.advance_u16()? == 0 {
+                break;
+            }
+        }
+        Ok(decode_utf16le(&self.bytes[start..self.pos - 2]))
+    }
+
+    fn advance_wstring_prefixed(&mut self) -> Result<String> {
+        let len = self.advance_u16()? as usize;
+        let raw = self.take(len * 2)?;
+        Ok(decode_utf16le(raw))
+    }
+
+    fn advance_cstring(&mut self) -> Result<String> {
+        let start = self.pos;
+        loop {
+            if self.advance_u8()? == 0 {
+                break;
+            }
+        }
+        Ok(String::from_utf8_lossy(&self.bytes[start..self.pos - 1]).into_owned())
+    }
+}
+
+fn decode_utf16le(raw: &[u8]) -> String {
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Parse a Python-facing field type name into a [`FieldType`]
+pub(crate) fn field_type_from_str(value: &str) -> PyResult<FieldType> {
+    match value.to_lowercase().as_str() {
+        "u8" => Ok(FieldType::U8),
+        "i8" => Ok(FieldType::I8),
+        "u16" => Ok(FieldType::U16),
+        "u32" => Ok(FieldType::U32),
+        "u64" => Ok(FieldType::U64),
+        "i32" => Ok(FieldType::I32),
+        "i64" => Ok(FieldType::I64),
+        "f64" => Ok(FieldType::F64),
+        "guid" => Ok(FieldType::Guid),
+        "sid" => Ok(FieldType::Sid),
+        "wstring" => Ok(FieldType::WString(WStringEncoding::NullTerminated)),
+        "wstring_prefixed" => Ok(FieldType::WString(WStringEncoding::LengthPrefixed)),
+        "cstring" => Ok(FieldType::CString),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unknown field type: {other}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_fixed_width_fields() {
+        let guid = Uuid::new_v4();
+        let (data1, data2, data3, data4) = guid.as_fields();
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&42u32.to_le_bytes());
+        raw.extend_from_slice(&data1.to_le_bytes());
+        raw.extend_from_slice(&data2.to_le_bytes());
+        raw.extend_from_slice(&data3.to_le_bytes());
+        raw.extend_from_slice(data4);
+
+        let fields = vec![
+            ("Pid".to_string(), FieldType::U32),
+            ("ActivityId".to_string(), FieldType::Guid),
+        ];
+        let values = decode_fields(&fields, &raw).unwrap();
+        assert!(matches!(values["Pid"], EventValue::U32(42)));
+        assert!(matches!(&values["ActivityId"], EventValue::Guid(g) if *g == guid));
+    }
+
+    #[test]
+    fn test_decode_cstring_and_wstring() {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(b"hello\0");
+        raw.extend_from_slice(&"world".encode_utf16().flat_map(u16::to_le_bytes).collect::<Vec<u8>>());
+        raw.extend_from_slice(&0u16.to_le_bytes());
+
+        let fields = vec![
+            ("Name".to_string(), FieldType::CString),
+            (
+                "Message".to_string(),
+                FieldType::WString(WStringEncoding::NullTerminated),
+            ),
+        ];
+        let values = decode_fields(&fields, &raw).unwrap();
+        assert!(matches!(&values["Name"], EventValue::String(s) if s == "hello"));
+        assert!(matches!(&values["Message"], EventValue::String(s) if s == "world"));
+    }
+
+    #[test]
+    fn test_decode_truncated_errors() {
+        let fields = vec![("Pid".to_string(), FieldType::U32)];
+        assert!(decode_fields(&fields, &[0u8, 1]).is_err());
+    }
+
+    #[test]
+    fn test_register_and_decode_round_trip() {
+        let guid = Uuid::new_v4();
+        register_schema(
+            guid,
+            7,
+            1,
+            vec![("Code".to_string(), FieldType::U16)],
+        );
+        let raw = 9u16.to_le_bytes();
+        let values = decode(&guid, 7, 1, &raw).unwrap().unwrap();
+        assert!(matches!(values["Code"], EventValue::U16(9)));
+    }
+
+    #[test]
+    fn test_decode_unregistered_returns_none() {
+        assert!(decode(&Uuid::new_v4(), 1, 0, &[]).is_none());
+    }
+
+    #[test]
+    fn test_field_type_from_str_unknown_errors() {
+        assert!(field_type_from_str("bogus").is_err());
+    }
+}
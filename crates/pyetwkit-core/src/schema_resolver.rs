@@ -0,0 +1,467 @@
+//! TDH-backed schema resolution
+//!
+//! [`SchemaResolver`] turns a raw ETW event record into a full [`EventSchema`]
+//! by calling `TdhGetEventInformation` and walking the returned
+//! `TRACE_EVENT_INFO`'s property array, mapping each property's declared TDH
+//! `InType` to our [`PropertyType`]. Results are cached in a [`SchemaCache`]
+//! keyed by `(provider, event_id, version)`, like ferrisetw's own `Schema`,
+//! so repeated events of the same type skip the TDH round-trip.
+
+use crate::error::{EtwError, Result};
+use crate::schema::{
+    DecodingSource, EventSchema, PropertyInfo, PropertyType, SchemaCache, SchemaType,
+    SharedSchemaCache,
+};
+use crate::tlg;
+
+use parking_lot::RwLock;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::os::windows::ffi::OsStringExt;
+use std::sync::Arc;
+use uuid::Uuid;
+use windows::core::{GUID, PCWSTR};
+use windows::Win32::System::Diagnostics::Etw::{
+    TdhGetEventInformation, TdhGetEventMapInformation, DECODING_SOURCE, EVENT_HEADER_EXTENDED_DATA_ITEM,
+    EVENT_MAP_ENTRY, EVENT_MAP_INFO, EVENT_PROPERTY_INFO, EVENT_RECORD, TRACE_EVENT_INFO,
+};
+
+/// `EVENT_HEADER_EXTENDED_DATA_ITEM.ExtType` value carrying a TraceLogging
+/// provider's self-describing metadata blob (`evntcons.h`'s
+/// `EVENT_HEADER_EXT_TYPE_EVENT_SCHEMA_TL`), hardcoded locally for the same
+/// reason as [`ERROR_INSUFFICIENT_BUFFER`] above
+const EVENT_HEADER_EXT_TYPE_EVENT_SCHEMA_TL: u16 = 11;
+
+/// `TdhGetEventInformation`/`TdhEnumerateProviders` share this "call twice,
+/// first to size the buffer" convention; ERROR_INSUFFICIENT_BUFFER is
+/// expected on the first call
+const ERROR_INSUFFICIENT_BUFFER: u32 = 122;
+
+/// `EVENT_PROPERTY_INFO.Flags` bit meaning the property's element count is
+/// itself given by another property, rather than a fixed literal count
+const PROPERTY_PARAM_COUNT: u32 = 0x4;
+
+/// Byte offset of the `InType` field within `EVENT_PROPERTY_INFO`'s
+/// overlapping `nonStructType`/`structType`/`customSchemaType` union, which
+/// follows the `Flags: u32` and `NameOffset: u32` fields
+const PROPERTY_INFO_IN_TYPE_OFFSET: usize = 8;
+/// Byte offset of the `count`/`countPropertyIndex` union, immediately after
+/// the 8-byte type union above
+const PROPERTY_INFO_COUNT_OFFSET: usize = 16;
+/// Byte offset of `MapNameOffset`, within the same `nonStructType` union as
+/// `InType`/`OutType`
+const PROPERTY_INFO_MAP_NAME_OFFSET: usize = 12;
+
+/// `EVENT_MAP_INFO.Flag` bit meaning the map is an enumerated value map
+const EVENTMAP_INFO_FLAG_MANIFEST_VALUEMAP: u32 = 0x1;
+/// `EVENT_MAP_INFO.Flag` bit meaning the map is a bitmask
+const EVENTMAP_INFO_FLAG_MANIFEST_BITMAP: u32 = 0x2;
+
+/// TDH `InType` codes (`tdh.h`), hardcoded locally since they're small,
+/// stable numeric constants rather than anything windows-rs re-exports with
+/// a name we can rely on
+mod tdh_in_type {
+    pub const UNICODE_STRING: u16 = 1;
+    pub const ANSI_STRING: u16 = 2;
+    pub const INT8: u16 = 3;
+    pub const UINT8: u16 = 4;
+    pub const INT16: u16 = 5;
+    pub const UINT16: u16 = 6;
+    pub const INT32: u16 = 7;
+    pub const UINT32: u16 = 8;
+    pub const INT64: u16 = 9;
+    pub const UINT64: u16 = 10;
+    pub const FLOAT: u16 = 11;
+    pub const DOUBLE: u16 = 12;
+    pub const BOOLEAN: u16 = 13;
+    pub const BINARY: u16 = 14;
+    pub const GUID: u16 = 15;
+    pub const POINTER: u16 = 17;
+    pub const FILETIME: u16 = 18;
+    pub const SYSTEMTIME: u16 = 19;
+    pub const SID: u16 = 20;
+    pub const HEX_INT32: u16 = 21;
+    pub const HEX_INT64: u16 = 22;
+}
+
+/// Resolves event schemas via TDH, caching results so repeated events of the
+/// same `(provider, event_id, version)` skip the `TdhGetEventInformation`
+/// round-trip
+pub struct SchemaResolver {
+    cache: SharedSchemaCache,
+}
+
+impl SchemaResolver {
+    /// Create a resolver backed by its own, private cache
+    pub fn new() -> Self {
+        Self {
+            cache: Arc::new(RwLock::new(SchemaCache::new())),
+        }
+    }
+
+    /// Create a resolver backed by an existing shared cache, e.g. one a
+    /// session also consults directly when parsing event properties
+    pub fn with_cache(cache: SharedSchemaCache) -> Self {
+        Self { cache }
+    }
+
+    /// Shared handle to this resolver's cache
+    pub fn cache(&self) -> SharedSchemaCache {
+        Arc::clone(&self.cache)
+    }
+
+    /// Resolve the schema for `record`, returning a cached copy if one was
+    /// already resolved for this `(provider, event_id, version)`, and
+    /// populating the cache via TDH otherwise
+    pub fn resolve(&self, record: &EVENT_RECORD) -> Result<EventSchema> {
+        let provider_id = guid_to_string(record.EventHeader.ProviderId);
+        let event_id = record.EventHeader.EventDescriptor.Id;
+        let version = record.EventHeader.EventDescriptor.Version;
+
+        if let Some(schema) = self.cache.read().get(&provider_id, event_id, version) {
+            return Ok(schema.clone());
+        }
+
+        // TDH can't resolve manifest-free TraceLogging events; fall back to
+        // decoding the event's own inline TraceLogging metadata blob
+        let schema = match resolve_via_tdh(record, provider_id.clone(), event_id, version) {
+            Ok(schema) => schema,
+            Err(tdh_err) => match find_tlg_metadata(record) {
+                Some(blob) => tlg::decode_tlg_metadata(&provider_id, event_id, version, blob)?,
+                None => return Err(tdh_err),
+            },
+        };
+        self.cache.write().insert(schema.clone());
+        Ok(schema)
+    }
+}
+
+/// Find the `EVENT_HEADER_EXT_TYPE_EVENT_SCHEMA_TL` header extension item on
+/// `record`, if present, and return its raw metadata blob
+fn find_tlg_metadata(record: &EVENT_RECORD) -> Option<&[u8]> {
+    if record.ExtendedData.is_null() || record.ExtendedDataCount == 0 {
+        return None;
+    }
+
+    unsafe {
+        let items = std::slice::from_raw_parts(
+            record.ExtendedData,
+            record.ExtendedDataCount as usize,
+        );
+        items.iter().find_map(|item: &EVENT_HEADER_EXTENDED_DATA_ITEM| {
+            if item.ExtType != EVENT_HEADER_EXT_TYPE_EVENT_SCHEMA_TL || item.DataSize == 0 {
+                return None;
+            }
+            Some(std::slice::from_raw_parts(
+                item.DataPtr as *const u8,
+                item.DataSize as usize,
+            ))
+        })
+    }
+}
+
+impl Default for SchemaResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Call `TdhGetEventInformation` and walk the resulting `TRACE_EVENT_INFO`
+/// into an [`EventSchema`]
+fn resolve_via_tdh(
+    record: &EVENT_RECORD,
+    provider_id: String,
+    event_id: u16,
+    version: u8,
+) -> Result<EventSchema> {
+    unsafe {
+        let mut buffer_size: u32 = 0;
+        let probe = TdhGetEventInformation(record, None, None, &mut buffer_size);
+        if probe != 0 && probe != ERROR_INSUFFICIENT_BUFFER {
+            return Err(EtwError::TdhError(format!(
+                "TdhGetEventInformation failed: {probe}"
+            )));
+        }
+        if buffer_size == 0 {
+            return Err(EtwError::TdhError(
+                "TdhGetEventInformation returned an empty schema".to_string(),
+            ));
+        }
+
+        let mut buffer: Vec<u8> = vec![0u8; buffer_size as usize];
+        let info_ptr = buffer.as_mut_ptr() as *mut TRACE_EVENT_INFO;
+        let result = TdhGetEventInformation(record, None, Some(info_ptr), &mut buffer_size);
+        if result != 0 {
+            return Err(EtwError::TdhError(format!(
+                "TdhGetEventInformation failed: {result}"
+            )));
+        }
+
+        let info = &*info_ptr;
+        let base = info_ptr as *const u8;
+        let schema_type = decoding_source_to_schema_type(info.DecodingSource);
+        let decoding_source = decoding_source_to_decoding_source(info.DecodingSource);
+        let event_name = read_wide_string_at(base, info.EventNameOffset);
+
+        let property_count = info.TopLevelPropertyCount as usize;
+        let array_offset = std::mem::offset_of!(TRACE_EVENT_INFO, EventPropertyInfoArray);
+        let entry_size = std::mem::size_of::<EVENT_PROPERTY_INFO>();
+        let array_base = base.add(array_offset);
+
+        let mut properties = Vec::with_capacity(property_count);
+        for i in 0..property_count {
+            let entry = array_base.add(i * entry_size);
+            let flags = *(entry as *const u32);
+            let name_offset = *(entry.add(4) as *const u32);
+            let in_type = *(entry.add(PROPERTY_INFO_IN_TYPE_OFFSET) as *const u16);
+            let count = *(entry.add(PROPERTY_INFO_COUNT_OFFSET) as *const u16);
+            let map_name_offset = *(entry.add(PROPERTY_INFO_MAP_NAME_OFFSET) as *const u32);
+
+            let map_name = read_wide_string_at(base, map_name_offset);
+            let (value_map, is_bitmap) = match resolve_value_map(record, &map_name) {
+                Some((map, is_bitmap)) => (Some(map), is_bitmap),
+                None => (None, false),
+            };
+
+            properties.push(PropertyInfo {
+                name: read_wide_string_at(base, name_offset),
+                property_type: in_type_to_property_type(in_type),
+                length: None,
+                is_array: (flags & PROPERTY_PARAM_COUNT != 0) || count > 1,
+                value_map,
+                is_bitmap,
+            });
+        }
+
+        Ok(EventSchema {
+            provider_id,
+            event_id,
+            version,
+            event_name: if event_name.is_empty() {
+                None
+            } else {
+                Some(event_name)
+            },
+            properties,
+            schema_type,
+            decoding_source,
+        })
+    }
+}
+
+/// Resolve a property's `MapNameOffset` into its enum/bitmap value map via
+/// `TdhGetEventMapInformation`. Returns `None` if the property has no map
+/// (`map_name` empty) or TDH has nothing registered for it.
+fn resolve_value_map(record: &EVENT_RECORD, map_name: &str) -> Option<(HashMap<u64, String>, bool)> {
+    if map_name.is_empty() {
+        return None;
+    }
+
+    unsafe {
+        let mut map_name_wide: Vec<u16> = map_name.encode_utf16().chain(std::iter::once(0)).collect();
+        let map_name_ptr = PCWSTR(map_name_wide.as_mut_ptr());
+
+        let mut buffer_size: u32 = 0;
+        let probe = TdhGetEventMapInformation(record, map_name_ptr, None, &mut buffer_size);
+        if (probe != 0 && probe != ERROR_INSUFFICIENT_BUFFER) || buffer_size == 0 {
+            return None;
+        }
+
+        let mut buffer: Vec<u8> = vec![0u8; buffer_size as usize];
+        let info_ptr = buffer.as_mut_ptr() as *mut EVENT_MAP_INFO;
+        let result = TdhGetEventMapInformation(record, map_name_ptr, Some(info_ptr), &mut buffer_size);
+        if result != 0 {
+            return None;
+        }
+
+        let info = &*info_ptr;
+        let flag = info.Flag.0 as u32;
+        let is_bitmap = match flag {
+            f if f & EVENTMAP_INFO_FLAG_MANIFEST_BITMAP != 0 => true,
+            f if f & EVENTMAP_INFO_FLAG_MANIFEST_VALUEMAP != 0 => false,
+            _ => false,
+        };
+
+        let base = info_ptr as *const u8;
+        let entry_offset = std::mem::offset_of!(EVENT_MAP_INFO, MapEntryArray);
+        let entry_size = std::mem::size_of::<EVENT_MAP_ENTRY>();
+        let array_base = base.add(entry_offset);
+
+        let mut map = HashMap::with_capacity(info.EntryCount as usize);
+        for i in 0..info.EntryCount as usize {
+            let entry = array_base.add(i * entry_size);
+            let output_offset = *(entry as *const u32);
+            let value = *(entry.add(4) as *const u32);
+            map.insert(value as u64, read_wide_string_at(base, output_offset));
+        }
+
+        Some((map, is_bitmap))
+    }
+}
+
+/// Map a TDH `DecodingSource` to our [`SchemaType`]
+fn decoding_source_to_schema_type(source: DECODING_SOURCE) -> SchemaType {
+    match source.0 {
+        0 => SchemaType::Manifest,
+        1 => SchemaType::Mof,
+        3 => SchemaType::TraceLogging,
+        _ => SchemaType::Unknown,
+    }
+}
+
+/// Map a TDH `DecodingSource` to our [`DecodingSource`]
+fn decoding_source_to_decoding_source(source: DECODING_SOURCE) -> DecodingSource {
+    match source.0 {
+        0 => DecodingSource::XmlFile,
+        1 => DecodingSource::Wbem,
+        2 => DecodingSource::Wpp,
+        3 => DecodingSource::Tlg,
+        _ => DecodingSource::Unknown,
+    }
+}
+
+/// Map a TDH `InType` code to our [`PropertyType`]
+fn in_type_to_property_type(in_type: u16) -> PropertyType {
+    match in_type {
+        tdh_in_type::UNICODE_STRING | tdh_in_type::ANSI_STRING => PropertyType::String,
+        tdh_in_type::INT8 => PropertyType::Int8,
+        tdh_in_type::UINT8 => PropertyType::UInt8,
+        tdh_in_type::INT16 => PropertyType::Int16,
+        tdh_in_type::UINT16 => PropertyType::UInt16,
+        tdh_in_type::INT32 => PropertyType::Int32,
+        tdh_in_type::UINT32 => PropertyType::UInt32,
+        tdh_in_type::INT64 => PropertyType::Int64,
+        tdh_in_type::UINT64 => PropertyType::UInt64,
+        tdh_in_type::FLOAT => PropertyType::Float,
+        tdh_in_type::DOUBLE => PropertyType::Double,
+        tdh_in_type::BOOLEAN => PropertyType::Boolean,
+        tdh_in_type::BINARY => PropertyType::Binary,
+        tdh_in_type::GUID => PropertyType::Guid,
+        tdh_in_type::POINTER => PropertyType::Pointer,
+        tdh_in_type::FILETIME => PropertyType::FileTime,
+        tdh_in_type::SYSTEMTIME => PropertyType::SystemTime,
+        tdh_in_type::SID => PropertyType::Sid,
+        tdh_in_type::HEX_INT32 => PropertyType::HexInt32,
+        tdh_in_type::HEX_INT64 => PropertyType::HexInt64,
+        _ => PropertyType::Unknown,
+    }
+}
+
+/// Convert a Windows GUID to its string form
+fn guid_to_string(guid: GUID) -> String {
+    Uuid::from_fields(guid.data1, guid.data2, guid.data3, &guid.data4).to_string()
+}
+
+/// Read a null-terminated wide string at `offset` bytes from `base`, or an
+/// empty string if `offset` is `0` (TDH's convention for "not present")
+unsafe fn read_wide_string_at(base: *const u8, offset: u32) -> String {
+    if offset == 0 {
+        return String::new();
+    }
+
+    let ptr = base.add(offset as usize) as *const u16;
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+
+    let slice = std::slice::from_raw_parts(ptr, len);
+    OsString::from_wide(slice).to_string_lossy().into_owned()
+}
+
+/// Python wrapper for [`SchemaResolver`]
+#[pyclass(name = "SchemaResolver")]
+pub struct PySchemaResolver {
+    inner: SchemaResolver,
+}
+
+#[pymethods]
+impl PySchemaResolver {
+    #[new]
+    fn new() -> Self {
+        Self {
+            inner: SchemaResolver::new(),
+        }
+    }
+
+    /// Look up a previously resolved schema by `(provider_id, event_id,
+    /// version)`. Schemas are only populated as live events are resolved
+    /// through this resolver, so this returns `None` until a matching event
+    /// has been seen.
+    fn get(
+        &self,
+        provider_id: &str,
+        event_id: u16,
+        version: u8,
+    ) -> Option<crate::schema::PyEventSchema> {
+        self.inner
+            .cache
+            .read()
+            .get(provider_id, event_id, version)
+            .cloned()
+            .map(crate::schema::PyEventSchema::from)
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.cache.read().len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SchemaResolver(cached={})", self.inner.cache.read().len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_type_to_property_type() {
+        assert_eq!(
+            in_type_to_property_type(tdh_in_type::UNICODE_STRING),
+            PropertyType::String
+        );
+        assert_eq!(
+            in_type_to_property_type(tdh_in_type::UINT32),
+            PropertyType::UInt32
+        );
+        assert_eq!(in_type_to_property_type(9999), PropertyType::Unknown);
+    }
+
+    #[test]
+    fn test_decoding_source_to_decoding_source() {
+        assert_eq!(
+            decoding_source_to_decoding_source(DECODING_SOURCE(0)),
+            DecodingSource::XmlFile
+        );
+        assert_eq!(
+            decoding_source_to_decoding_source(DECODING_SOURCE(2)),
+            DecodingSource::Wpp
+        );
+        assert_eq!(
+            decoding_source_to_decoding_source(DECODING_SOURCE(42)),
+            DecodingSource::Unknown
+        );
+    }
+
+    #[test]
+    fn test_decoding_source_to_schema_type() {
+        assert_eq!(
+            decoding_source_to_schema_type(DECODING_SOURCE(0)),
+            SchemaType::Manifest
+        );
+        assert_eq!(
+            decoding_source_to_schema_type(DECODING_SOURCE(1)),
+            SchemaType::Mof
+        );
+        assert_eq!(
+            decoding_source_to_schema_type(DECODING_SOURCE(3)),
+            SchemaType::TraceLogging
+        );
+        assert_eq!(
+            decoding_source_to_schema_type(DECODING_SOURCE(42)),
+            SchemaType::Unknown
+        );
+    }
+}
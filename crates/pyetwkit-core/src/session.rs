@@ -4,6 +4,9 @@ use crate::error::{EtwError, Result};
 use crate::event::{EtwEvent, EventValue, PyEtwEvent};
 use crate::filter::EventFilter;
 use crate::provider::{EtwProvider, PyEtwProvider, TraceLevel};
+use crate::schema::{EventSchema, SchemaCache, SharedSchemaCache};
+use crate::schema_registry;
+use crate::spill::SpillQueue;
 use crate::stats::{PySessionStats, SessionStats, SharedStatsTracker, StatsTracker};
 
 use chrono::{TimeZone, Utc};
@@ -12,17 +15,32 @@ use ferrisetw::parser::Parser;
 use ferrisetw::provider::Provider;
 use ferrisetw::schema::Schema;
 use ferrisetw::schema_locator::SchemaLocator;
-use ferrisetw::trace::{stop_trace_by_name, TraceTrait, UserTrace};
+use ferrisetw::trace::{stop_trace_by_name, FileTrace, TraceTrait, UserTrace};
 use ferrisetw::EventRecord;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 use uuid::Uuid;
+use windows::Win32::System::Diagnostics::Etw::EVENT_RECORD;
+
+/// Identifies a [`EtwSession::subscribe`] registration, for use with [`EtwSession::unsubscribe`]
+pub type SubscriptionId = u64;
+
+/// Per-event callback registered via [`EtwSession::subscribe`]
+type EventCallback = Box<dyn Fn(&EtwEvent) + Send + Sync>;
+
+/// Registered subscribers, dispatched to from a dedicated thread that owns `event_rx`
+#[derive(Default)]
+struct Subscribers {
+    next_id: AtomicU64,
+    callbacks: Mutex<Vec<(SubscriptionId, EventCallback)>>,
+}
 
 /// Trace mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -34,6 +52,25 @@ pub enum TraceMode {
     File,
 }
 
+/// How to handle an event that doesn't fit in the bounded event channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum OverflowPolicy {
+    /// Drop the incoming event and count it as lost (default, current behavior)
+    #[default]
+    DropNewest,
+    /// Drop the oldest buffered event to make room for the incoming one
+    DropOldest,
+    /// Block the processing thread until the channel has room
+    Block,
+    /// Serialize the incoming event to a disk-backed spill queue, to be
+    /// replayed once the channel has room again
+    SpillToFile,
+}
+
+/// How long [`OverflowPolicy::Block`] waits for channel room before giving
+/// up and counting the event as lost
+const BLOCK_SEND_TIMEOUT: Duration = Duration::from_millis(500);
+
 /// Session configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionConfig {
@@ -53,6 +90,12 @@ pub struct SessionConfig {
     pub stop_if_exists: bool,
     /// Event channel capacity
     pub channel_capacity: usize,
+    /// ETL files to replay, in order, when `mode` is [`TraceMode::File`]
+    pub etl_paths: Vec<String>,
+    /// How to handle events that don't fit in the bounded event channel
+    pub overflow_policy: OverflowPolicy,
+    /// Spill file path, required when `overflow_policy` is [`OverflowPolicy::SpillToFile`]
+    pub spill_path: Option<String>,
 }
 
 impl Default for SessionConfig {
@@ -69,6 +112,9 @@ impl Default for SessionConfig {
             flush_timer_secs: 1,
             stop_if_exists: true,
             channel_capacity: 10000,
+            etl_paths: Vec::new(),
+            overflow_policy: OverflowPolicy::DropNewest,
+            spill_path: None,
         }
     }
 }
@@ -89,6 +135,79 @@ struct SessionHandle {
     stop_flag: Arc<AtomicBool>,
 }
 
+/// Resolved overflow-handling state, built once per `start()` call and
+/// captured by the trace callback closure
+#[derive(Clone)]
+struct OverflowHandler {
+    policy: OverflowPolicy,
+    event_rx: Receiver<EtwEvent>,
+    spill: Option<Arc<SpillQueue>>,
+}
+
+impl OverflowHandler {
+    /// Resolve the configured policy into callback-ready state. `event_rx`
+    /// is a clone of the session's channel receiver, used by `DropOldest` to
+    /// make room without disturbing the session's own consumer handle.
+    fn build(config: &SessionConfig, event_rx: Receiver<EtwEvent>) -> Result<Self> {
+        let spill = match config.overflow_policy {
+            OverflowPolicy::SpillToFile => {
+                let path = config.spill_path.clone().ok_or_else(|| {
+                    EtwError::InvalidConfig(
+                        "OverflowPolicy::SpillToFile requires spill_path".to_string(),
+                    )
+                })?;
+                Some(Arc::new(SpillQueue::open(path)?))
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            policy: config.overflow_policy,
+            event_rx,
+            spill,
+        })
+    }
+
+    /// Handle an event that didn't fit in the bounded channel, per the configured policy
+    fn handle_overflow(&self, event: EtwEvent, event_tx: &Sender<EtwEvent>, stats: &SharedStatsTracker) {
+        match self.policy {
+            OverflowPolicy::DropNewest => {
+                stats.record_events_lost(1);
+            }
+            OverflowPolicy::DropOldest => {
+                let _ = self.event_rx.try_recv();
+                if event_tx.try_send(event).is_err() {
+                    stats.record_events_lost(1);
+                }
+            }
+            OverflowPolicy::Block => {
+                if event_tx.send_timeout(event, BLOCK_SEND_TIMEOUT).is_err() {
+                    stats.record_events_lost(1);
+                }
+            }
+            OverflowPolicy::SpillToFile => match &self.spill {
+                Some(spill) if spill.push(&event).is_ok() => {
+                    stats.record_event_spilled();
+                }
+                _ => stats.record_events_lost(1),
+            },
+        }
+    }
+
+    /// Opportunistically replay one spilled event now that the channel had
+    /// room for the event that was just sent. A no-op unless `spill` is set.
+    fn recover_one(&self, event_tx: &Sender<EtwEvent>, stats: &SharedStatsTracker) {
+        let Some(spill) = &self.spill else {
+            return;
+        };
+        if let Ok(Some(event)) = spill.pop_one() {
+            if event_tx.try_send(event).is_ok() {
+                stats.record_event_recovered();
+            }
+        }
+    }
+}
+
 /// ETW Session
 pub struct EtwSession {
     config: SessionConfig,
@@ -98,6 +217,9 @@ pub struct EtwSession {
     event_tx: Option<Sender<EtwEvent>>,
     stats: SharedStatsTracker,
     handle: Option<SessionHandle>,
+    subscribers: Arc<Subscribers>,
+    dispatcher_handle: Option<JoinHandle<()>>,
+    schema_cache: SharedSchemaCache,
 }
 
 impl EtwSession {
@@ -123,6 +245,9 @@ impl EtwSession {
             event_tx: Some(tx),
             stats,
             handle: None,
+            subscribers: Arc::new(Subscribers::default()),
+            dispatcher_handle: None,
+            schema_cache: Arc::new(RwLock::new(SchemaCache::new())),
         }
     }
 
@@ -132,6 +257,14 @@ impl EtwSession {
         self
     }
 
+    /// Shared handle to this session's schema cache. Event parsing consults
+    /// it for a resolved property layout before falling back to guessing at
+    /// well-known property names; a schema resolver can populate it from the
+    /// outside via this handle.
+    pub fn schema_cache(&self) -> SharedSchemaCache {
+        Arc::clone(&self.schema_cache)
+    }
+
     /// Start the trace session
     pub fn start(&mut self) -> Result<()> {
         let state = *self.state.read();
@@ -139,6 +272,28 @@ impl EtwSession {
             return Err(EtwError::SessionAlreadyRunning);
         }
 
+        match self.config.mode {
+            TraceMode::RealTime => self.start_realtime(),
+            TraceMode::File => self.start_file_replay(),
+        }
+    }
+
+    /// Start a live real-time trace session
+    fn start_realtime(&mut self) -> Result<()> {
+        // Kernel-flagged providers (see `EtwProvider::with_kernel_flags`) are
+        // selected by EVENT_TRACE_FLAG_* group through the NT Kernel Logger,
+        // which only `KernelTrace` talks to — `UserTrace`/`Provider::by_guid`
+        // has no way to honor them. Rather than silently enabling such a
+        // provider with none of its kernel flags in effect, reject it up
+        // front and point callers at `KernelSession`.
+        if let Some(provider) = self.providers.iter().find(|p| p.kernel_flags != 0) {
+            return Err(EtwError::InvalidConfig(format!(
+                "provider {} has kernel_flags set ({:#x}); kernel-flagged providers aren't \
+                 supported via EtwSession — use KernelSession instead",
+                provider.guid, provider.kernel_flags
+            )));
+        }
+
         // Stop existing session if configured
         if self.config.stop_if_exists {
             let _ = stop_trace_by_name(&self.config.name);
@@ -151,28 +306,55 @@ impl EtwSession {
             .event_tx
             .clone()
             .ok_or(EtwError::Internal("No event channel".into()))?;
+        let event_rx = self
+            .event_rx
+            .clone()
+            .ok_or(EtwError::Internal("No event channel".into()))?;
+        let overflow = OverflowHandler::build(&self.config, event_rx)?;
         let stats = self.stats.clone();
         let state_clone = self.state.clone();
+        let schema_cache = self.schema_cache();
         let stop_flag = Arc::new(AtomicBool::new(false));
         let stop_flag_clone = stop_flag.clone();
 
+        // Scoped the same way file replay's `matches_provider_filters` is:
+        // ferrisetw's own pushdown (below, in the provider-enable loop) only
+        // forwards `EventIds`/`ProcessId` to the OS, so the full filter set
+        // — `ProcessName`/`ExcludeEventIds`/`Custom`/`Property`/combinators —
+        // still needs to run against the decoded event here.
+        let provider_filters: Vec<(Uuid, Vec<EventFilter>)> = self
+            .providers
+            .iter()
+            .map(|p| (p.guid, p.filters.clone()))
+            .collect();
+
         // Create callback closure
         let callback = move |record: &EventRecord, schema_locator: &SchemaLocator| {
-            stats.record_event_received();
+            let provider_id = guid_to_uuid(record.provider_id());
+            stats.record_event_received(provider_id);
 
             // Try to resolve schema (ferrisetw 1.2: event_schema returns Result)
             let schema = schema_locator.event_schema(record).ok();
 
             // Parse event
-            let event = parse_event_record(record, schema.as_ref().map(|s| s.as_ref()));
+            let event = parse_event_record(
+                record,
+                schema.as_ref().map(|s| s.as_ref()),
+                Some(&schema_cache.read()),
+            );
+
+            if !matches_provider_filters(&provider_filters, &event) {
+                return;
+            }
 
             // Send to channel
             match event_tx.try_send(event) {
                 Ok(_) => {
-                    stats.record_event_processed();
+                    stats.record_event_processed(provider_id);
+                    overflow.recover_one(&event_tx, &stats);
                 }
-                Err(TrySendError::Full(_)) => {
-                    stats.record_events_lost(1);
+                Err(TrySendError::Full(event)) => {
+                    overflow.handle_overflow(event, &event_tx, &stats);
                 }
                 Err(TrySendError::Disconnected(_)) => {
                     // Channel closed, stop processing
@@ -205,6 +387,12 @@ impl EtwSession {
                 prov_builder = prov_builder.all(provider.keywords_all);
             }
 
+            // Pass the EVENT_ENABLE_PROPERTY_* bitmask through to
+            // EnableTraceEx2's EnableParameters.EnableProperty
+            if provider.enable_properties != 0 {
+                prov_builder = prov_builder.trace_flags(provider.enable_properties);
+            }
+
             // Add event ID filters
             for filter in &provider.filters {
                 match filter {
@@ -251,6 +439,101 @@ impl EtwSession {
         Ok(())
     }
 
+    /// Replay one or more ETL files through the same decode/filter/stats
+    /// pipeline a live session uses, feeding the same `event_tx` channel
+    fn start_file_replay(&mut self) -> Result<()> {
+        if self.config.etl_paths.is_empty() {
+            return Err(EtwError::InvalidConfig(
+                "TraceMode::File requires at least one entry in etl_paths".to_string(),
+            ));
+        }
+        for path in &self.config.etl_paths {
+            if !Path::new(path).exists() {
+                return Err(EtwError::FileNotFound(path.clone()));
+            }
+        }
+
+        let event_tx = self
+            .event_tx
+            .clone()
+            .ok_or(EtwError::Internal("No event channel".into()))?;
+        let event_rx = self
+            .event_rx
+            .clone()
+            .ok_or(EtwError::Internal("No event channel".into()))?;
+        let overflow = OverflowHandler::build(&self.config, event_rx)?;
+        let stats = self.stats.clone();
+        let state_clone = self.state.clone();
+        let schema_cache = self.schema_cache();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let stop_flag_clone = stop_flag.clone();
+
+        // Only EventIds/ProcessId are honored, same as the live pushdown path.
+        // Keep each provider's filter list scoped to its own GUID so an event
+        // only has to satisfy the filters of the provider it came from, not
+        // every provider's filters ANDed together.
+        let provider_filters: Vec<(Uuid, Vec<EventFilter>)> = self
+            .providers
+            .iter()
+            .map(|p| (p.guid, p.filters.clone()))
+            .collect();
+        let paths = self.config.etl_paths.clone();
+
+        let trace_thread = thread::spawn(move || {
+            for path in paths {
+                if stop_flag.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let event_tx = event_tx.clone();
+                let stats = stats.clone();
+                let provider_filters = provider_filters.clone();
+                let overflow = overflow.clone();
+                let schema_cache = schema_cache.clone();
+                let callback = move |record: &EventRecord, schema_locator: &SchemaLocator| {
+                    let provider_id = guid_to_uuid(record.provider_id());
+                    stats.record_event_received(provider_id);
+
+                    let schema = schema_locator.event_schema(record).ok();
+                    let event = parse_event_record(
+                        record,
+                        schema.as_ref().map(|s| s.as_ref()),
+                        Some(&schema_cache.read()),
+                    );
+
+                    if !matches_provider_filters(&provider_filters, &event) {
+                        return;
+                    }
+
+                    match event_tx.try_send(event) {
+                        Ok(_) => {
+                            stats.record_event_processed(provider_id);
+                            overflow.recover_one(&event_tx, &stats);
+                        }
+                        Err(TrySendError::Full(event)) => {
+                            overflow.handle_overflow(event, &event_tx, &stats);
+                        }
+                        Err(TrySendError::Disconnected(_)) => {}
+                    }
+                };
+
+                let trace_builder = FileTrace::new(PathBuf::from(path), callback);
+                let _ = trace_builder.start_and_process();
+            }
+
+            *state_clone.write() = SessionState::Stopped;
+        });
+
+        *self.state.write() = SessionState::Running;
+        self.handle = Some(SessionHandle {
+            trace: None,
+            trace_thread: Some(trace_thread),
+            stop_flag: stop_flag_clone,
+        });
+
+        Ok(())
+    }
+
     /// Stop the trace session
     pub fn stop(&mut self) -> Result<()> {
         let state = *self.state.read();
@@ -298,11 +581,50 @@ impl EtwSession {
         self.event_rx.as_ref()?.try_recv().ok()
     }
 
+    /// Register a callback invoked for every event, fanning out to all
+    /// concurrently registered subscribers. The first call to `subscribe`
+    /// hands `event_rx` off to a dedicated dispatcher thread, so it can no
+    /// longer be drained via `next_event`/`try_next_event` afterwards.
+    /// Returns a subscription id usable with [`Self::unsubscribe`].
+    pub fn subscribe(&mut self, callback: EventCallback) -> Result<SubscriptionId> {
+        if self.dispatcher_handle.is_none() {
+            let receiver = self.event_rx.take().ok_or_else(|| {
+                EtwError::InvalidConfig("Session has no event channel to subscribe to".to_string())
+            })?;
+            let subscribers = Arc::clone(&self.subscribers);
+            self.dispatcher_handle = Some(thread::spawn(move || {
+                while let Ok(event) = receiver.recv() {
+                    for (_, callback) in subscribers.callbacks.lock().iter() {
+                        callback(&event);
+                    }
+                }
+            }));
+        }
+
+        let id = self.subscribers.next_id.fetch_add(1, Ordering::Relaxed);
+        self.subscribers.callbacks.lock().push((id, callback));
+        Ok(id)
+    }
+
+    /// Unregister a subscription. Returns `false` if the id was unknown.
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut callbacks = self.subscribers.callbacks.lock();
+        let original_len = callbacks.len();
+        callbacks.retain(|(subscription_id, _)| *subscription_id != id);
+        callbacks.len() < original_len
+    }
+
     /// Get current statistics
     pub fn stats(&self) -> SessionStats {
         self.stats.snapshot()
     }
 
+    /// Shared handle to the stats tracker, for wiring into other Rust-side
+    /// components (e.g. [`crate::rules::PyRuleSet`])
+    pub(crate) fn shared_stats(&self) -> SharedStatsTracker {
+        self.stats.clone()
+    }
+
     /// Check if session is running
     pub fn is_running(&self) -> bool {
         *self.state.read() == SessionState::Running
@@ -323,12 +645,52 @@ impl Drop for EtwSession {
 }
 
 /// Convert ferrisetw GUID to uuid Uuid
-fn guid_to_uuid(guid: ferrisetw::GUID) -> Uuid {
+pub(crate) fn guid_to_uuid(guid: ferrisetw::GUID) -> Uuid {
     Uuid::from_u128(guid.to_u128())
 }
 
-/// Parse ferrisetw EventRecord to our EtwEvent
-pub fn parse_event_record(record: &EventRecord, schema: Option<&Schema>) -> EtwEvent {
+/// Check a decoded event against the full filter set registered on the
+/// session's providers, via the same [`EventFilter::matches_event`] entry
+/// point `etl_reader.rs` uses — not just the `EventIds`/`ProcessId` subset
+/// ferrisetw's own pushdown forwards to the OS — so `ProcessName`,
+/// `ExcludeEventIds`, `Custom`, `Property`, and combinator filters all take
+/// effect for both live capture and file replay, not just get silently
+/// treated as always-matching.
+///
+/// Each provider's filters are scoped to that provider's GUID, matching how
+/// `start_realtime` only enables the providers added to the session: an
+/// event must come from one of those providers (OR-across-providers) and
+/// satisfy all of *that* provider's filters (AND-within-a-provider). Events
+/// from providers the session never added are dropped, same as live
+/// capture, which only ever sees events from its enabled providers.
+fn matches_provider_filters(provider_filters: &[(Uuid, Vec<EventFilter>)], event: &EtwEvent) -> bool {
+    provider_filters
+        .iter()
+        .filter(|(guid, _)| *guid == event.provider_id)
+        .any(|(_, filters)| filters.iter().all(|filter| filter.matches_event(event)))
+}
+
+/// Parse an [`OverflowPolicy`] from its Python-facing string name
+fn overflow_policy_from_str(value: &str) -> PyResult<OverflowPolicy> {
+    match value.to_lowercase().as_str() {
+        "drop_newest" => Ok(OverflowPolicy::DropNewest),
+        "drop_oldest" => Ok(OverflowPolicy::DropOldest),
+        "block" => Ok(OverflowPolicy::Block),
+        "spill_to_file" => Ok(OverflowPolicy::SpillToFile),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unknown overflow policy: {other}"
+        ))),
+    }
+}
+
+/// Parse ferrisetw EventRecord to our EtwEvent. `schema_cache`, if given, is
+/// consulted for a resolved property layout (keyed by provider/event
+/// id/version) before falling back to guessing at well-known property names.
+pub fn parse_event_record(
+    record: &EventRecord,
+    schema: Option<&Schema>,
+    schema_cache: Option<&SchemaCache>,
+) -> EtwEvent {
     // ferrisetw 1.2 API: Direct access to event fields
     let provider_id = guid_to_uuid(record.provider_id());
 
@@ -359,10 +721,29 @@ pub fn parse_event_record(record: &EventRecord, schema: Option<&Schema>) -> EtwE
         event.activity_id = Some(guid_to_uuid(activity));
     }
 
+    // A declaratively registered custom schema (see
+    // `crate::schema_registry::register_schema`) takes priority: it exists
+    // specifically to decode events TDH has no manifest for at all, which is
+    // exactly the case where ferrisetw can't hand us a `Schema`/`Parser`
+    // below. ferrisetw's `EventRecord` doesn't expose a safe accessor for the
+    // raw user-data buffer, so we read it directly off the underlying
+    // `EVENT_RECORD` it wraps.
+    if let Some(Ok(values)) = schema_registry::decode(
+        &provider_id,
+        event.event_id,
+        event.version,
+        raw_user_data(record),
+    ) {
+        event.properties = values;
+        return event;
+    }
+
     // Parse properties using schema if available
     if let Some(schema) = schema {
         let parser = Parser::create(record, schema);
-        event.properties = parse_properties(&parser, schema);
+        let cached_schema = schema_cache
+            .and_then(|cache| cache.get(&provider_id.to_string(), event.event_id, event.version));
+        event.properties = parse_properties(&parser, cached_schema);
         event.provider_name = Some(schema.provider_name().to_string());
     }
     // Note: raw_data extraction removed as user_buffer is private in ferrisetw 1.2
@@ -370,47 +751,142 @@ pub fn parse_event_record(record: &EventRecord, schema: Option<&Schema>) -> EtwE
     event
 }
 
-/// Parse event properties from schema
-/// Note: ferrisetw 1.2 made properties() private, so we can't enumerate properties.
-/// Instead, we extract common known properties if they exist.
-fn parse_properties(parser: &Parser, _schema: &Schema) -> HashMap<String, EventValue> {
-    let mut properties = HashMap::new();
+/// Read an event's raw user-data buffer directly off the `EVENT_RECORD`
+/// ferrisetw's `EventRecord` wraps, since ferrisetw itself doesn't expose a
+/// safe accessor for it (see the note above)
+fn raw_user_data(record: &EventRecord) -> &[u8] {
+    let raw: &EVENT_RECORD = record;
+    unsafe { std::slice::from_raw_parts(raw.UserData as *const u8, raw.UserDataLength as usize) }
+}
 
-    // Try common property names that might exist in various events
-    let common_props = [
-        "ProcessId",
-        "ThreadId",
-        "ImageFileName",
-        "ProcessName",
-        "CommandLine",
-        "FileName",
-        "FilePath",
-        "Message",
-        "Data",
-        "Status",
-        "Result",
-        "ErrorCode",
-    ];
-
-    for name in common_props {
-        // Try different types for each property
-        if let Ok(v) = parser.try_parse::<String>(name) {
-            properties.insert(name.to_string(), EventValue::String(v));
-        } else if let Ok(v) = parser.try_parse::<u64>(name) {
-            properties.insert(name.to_string(), EventValue::U64(v));
-        } else if let Ok(v) = parser.try_parse::<u32>(name) {
-            properties.insert(name.to_string(), EventValue::U32(v));
-        } else if let Ok(v) = parser.try_parse::<i64>(name) {
-            properties.insert(name.to_string(), EventValue::I64(v));
-        } else if let Ok(v) = parser.try_parse::<i32>(name) {
-            properties.insert(name.to_string(), EventValue::I32(v));
+/// Parse event properties, preferring a resolved schema's declared property
+/// list over guessing at well-known names when one is cached for this event
+fn parse_properties(parser: &Parser, cached_schema: Option<&EventSchema>) -> HashMap<String, EventValue> {
+    match cached_schema {
+        Some(schema) => parse_properties_from_schema(parser, schema),
+        None => parse_properties_fallback(parser),
+    }
+}
+
+/// Decode every property declared by a cached [`EventSchema`], instead of
+/// guessing at well-known names — provider-specific fields the fallback
+/// list doesn't know about come through as long as a schema is cached.
+fn parse_properties_from_schema(parser: &Parser, schema: &EventSchema) -> HashMap<String, EventValue> {
+    let mut properties = HashMap::new();
+    for prop in &schema.properties {
+        if let Some(value) = try_parse_property(parser, &prop.name) {
+            properties.insert(prop.name.clone(), value);
         }
-        // Skip if property doesn't exist or can't be parsed
     }
+    properties
+}
 
+/// Property names with no resolved schema to consult yet. Broader than a
+/// single provider's fields since this runs across every provider that
+/// doesn't have a cached schema; kept grouped by the provider family each
+/// name is best known from.
+/// Note: ferrisetw 1.2 made properties() private, so we can't enumerate
+/// properties without a resolved schema. Until one is cached (see
+/// [`crate::schema::SharedSchemaCache`]), we probe this candidate list instead.
+const FALLBACK_PROPS: &[&str] = &[
+    // Generic / common across providers
+    "ProcessId",
+    "ThreadId",
+    "ImageFileName",
+    "ProcessName",
+    "CommandLine",
+    "FileName",
+    "FilePath",
+    "Message",
+    "Data",
+    "Status",
+    "Result",
+    "ErrorCode",
+    "Name",
+    "Path",
+    "Value",
+    "Size",
+    "Count",
+    "Flags",
+    "Type",
+    "Reason",
+    "Description",
+    "Source",
+    "Target",
+    // Kernel Process provider
+    "ParentId",
+    "SessionId",
+    "ExitStatus",
+    "UniqueProcessKey",
+    // Kernel Thread provider
+    "TThreadId",
+    "StackBase",
+    "StackLimit",
+    // Kernel Image/Module provider
+    "ImageBase",
+    "ImageSize",
+    "ImageChecksum",
+    // Kernel FileIo provider
+    "FileObject",
+    "FileKey",
+    "IrpPtr",
+    "TTID",
+    "ByteOffset",
+    "IoSize",
+    "IoFlags",
+    "ExtraInfo",
+    // Kernel Registry provider
+    "KeyName",
+    "KeyHandle",
+    "Index",
+    "InitialTime",
+    // Kernel TcpIp/UdpIp providers
+    "daddr",
+    "saddr",
+    "dport",
+    "sport",
+    "seqnum",
+    "connid",
+    // Kernel DiskIo provider
+    "DiskNumber",
+    "IrpFlags",
+    "TransferSize",
+    "HighResResponseTime",
+    // Kernel PageFault provider
+    "VirtualAddress",
+    "ProgramCounter",
+];
+
+/// Probe the static candidate list for properties that exist in this event.
+/// Used when no resolved schema is cached for the event yet.
+fn parse_properties_fallback(parser: &Parser) -> HashMap<String, EventValue> {
+    let mut properties = HashMap::new();
+    for name in FALLBACK_PROPS {
+        if let Some(value) = try_parse_property(parser, name) {
+            properties.insert((*name).to_string(), value);
+        }
+    }
     properties
 }
 
+/// Try each type ferrisetw's `Parser` can decode a named property as, in
+/// turn, returning the first that matches
+fn try_parse_property(parser: &Parser, name: &str) -> Option<EventValue> {
+    if let Ok(v) = parser.try_parse::<String>(name) {
+        Some(EventValue::String(v))
+    } else if let Ok(v) = parser.try_parse::<u64>(name) {
+        Some(EventValue::U64(v))
+    } else if let Ok(v) = parser.try_parse::<u32>(name) {
+        Some(EventValue::U32(v))
+    } else if let Ok(v) = parser.try_parse::<i64>(name) {
+        Some(EventValue::I64(v))
+    } else if let Ok(v) = parser.try_parse::<i32>(name) {
+        Some(EventValue::I32(v))
+    } else {
+        None
+    }
+}
+
 /// Python wrapper for EtwSession
 #[pyclass(name = "EtwSession")]
 pub struct PyEtwSession {
@@ -432,16 +908,23 @@ impl PyEtwSession {
         }
     }
 
-    /// Create a session with custom configuration
+    /// Create a session with custom configuration. Passing `etl_paths`
+    /// switches the session into `TraceMode::File`, replaying those files
+    /// through the same pipeline a live session uses instead of capturing.
+    /// `overflow_policy` is one of `"drop_newest"` (default), `"drop_oldest"`,
+    /// `"block"`, or `"spill_to_file"` (which requires `spill_path`).
     #[staticmethod]
-    #[pyo3(signature = (name=None, buffer_size_kb=64, min_buffers=64, max_buffers=128, channel_capacity=10000))]
+    #[pyo3(signature = (name=None, buffer_size_kb=64, min_buffers=64, max_buffers=128, channel_capacity=10000, etl_paths=None, overflow_policy=None, spill_path=None))]
     fn with_config(
         name: Option<String>,
         buffer_size_kb: u32,
         min_buffers: u32,
         max_buffers: u32,
         channel_capacity: usize,
-    ) -> Self {
+        etl_paths: Option<Vec<String>>,
+        overflow_policy: Option<&str>,
+        spill_path: Option<String>,
+    ) -> PyResult<Self> {
         let mut config = SessionConfig::default();
         if let Some(n) = name {
             config.name = n;
@@ -450,10 +933,18 @@ impl PyEtwSession {
         config.min_buffers = min_buffers;
         config.max_buffers = max_buffers;
         config.channel_capacity = channel_capacity;
+        if let Some(etl_paths) = etl_paths {
+            config.mode = TraceMode::File;
+            config.etl_paths = etl_paths;
+        }
+        if let Some(policy) = overflow_policy {
+            config.overflow_policy = overflow_policy_from_str(policy)?;
+        }
+        config.spill_path = spill_path;
 
-        Self {
+        Ok(Self {
             inner: Some(EtwSession::with_config(config)),
-        }
+        })
     }
 
     /// Add a provider
@@ -546,6 +1037,37 @@ impl PyEtwSession {
         Ok(session.try_next_event().map(PyEtwEvent::from))
     }
 
+    /// Register a callback invoked for every event from a dedicated
+    /// dispatcher thread; supports multiple concurrent subscribers. Returns
+    /// a subscription id usable with `unsubscribe()`.
+    fn subscribe(&mut self, callback: Py<PyAny>) -> PyResult<SubscriptionId> {
+        let session = self
+            .inner
+            .as_mut()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Session is closed"))?;
+
+        session
+            .subscribe(Box::new(move |event: &EtwEvent| {
+                Python::with_gil(|py| {
+                    let Ok(py_event) = Py::new(py, PyEtwEvent::from(event.clone())) else {
+                        return;
+                    };
+                    let _ = callback.call1(py, (py_event,));
+                });
+            }))
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+    }
+
+    /// Unregister a subscription previously returned by `subscribe()`.
+    /// Returns `False` if the id was unknown.
+    fn unsubscribe(&self, id: SubscriptionId) -> PyResult<bool> {
+        let session = self
+            .inner
+            .as_ref()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Session is closed"))?;
+        Ok(session.unsubscribe(id))
+    }
+
     /// Get session statistics
     fn stats(&self) -> PyResult<PySessionStats> {
         let session = self
@@ -566,6 +1088,68 @@ impl PyEtwSession {
         self.inner.as_ref().map(|s| s.name().to_string())
     }
 
+    /// Async iterator protocol: `async for event in session:`
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Await the next event, per the async iterator protocol; raises
+    /// `StopAsyncIteration` once the session's event channel is closed
+    fn __anext__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.get_event(py)
+    }
+
+    /// Return a `Future` that resolves with the next event. A dedicated
+    /// thread drains the event channel and resolves the future via
+    /// `call_soon_threadsafe`, so awaiting it never blocks the event loop.
+    fn get_event(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let session = self
+            .inner
+            .as_ref()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Session is closed"))?;
+        let receiver = session
+            .event_rx
+            .clone()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Session has no event channel"))?;
+
+        let asyncio = py.import_bound("asyncio")?;
+        let event_loop = asyncio.call_method0("get_event_loop")?;
+        let future = event_loop.call_method0("create_future")?;
+        let future_handle = future.clone().unbind();
+        let event_loop_handle = event_loop.unbind();
+
+        thread::spawn(move || {
+            let event = receiver.recv().ok();
+            Python::with_gil(|py| {
+                let resolve = |py: Python<'_>| -> PyResult<()> {
+                    let future = future_handle.bind(py);
+                    let event_loop = event_loop_handle.bind(py);
+
+                    let (setter, value) = match event {
+                        Some(event) => (
+                            future.getattr("set_result")?.unbind(),
+                            Py::new(py, PyEtwEvent::from(event))?.into_bound(py).into_any().unbind(),
+                        ),
+                        None => {
+                            let stop_iteration = py
+                                .get_type_bound::<pyo3::exceptions::PyStopAsyncIteration>()
+                                .call0()?
+                                .unbind();
+                            (future.getattr("set_exception")?.unbind(), stop_iteration)
+                        }
+                    };
+                    event_loop
+                        .call_method1("call_soon_threadsafe", (setter, value))?
+                        .unbind();
+                    Ok(())
+                };
+                let _ = resolve(py);
+            });
+        });
+
+        Ok(future.unbind())
+    }
+
     /// Context manager enter
     fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
         slf
@@ -599,6 +1183,18 @@ impl PyEtwSession {
     }
 }
 
+impl PyEtwSession {
+    /// Shared handle to the session's stats tracker, for wiring into other
+    /// Rust-side components (e.g. [`crate::rules::PyRuleSet`])
+    pub(crate) fn shared_stats(&self) -> PyResult<SharedStatsTracker> {
+        let session = self
+            .inner
+            .as_ref()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Session is closed"))?;
+        Ok(session.shared_stats())
+    }
+}
+
 /// Register raw API functions for direct ETW access
 pub fn register_raw_api(m: &Bound<'_, PyModule>) -> PyResult<()> {
     /// Stop a trace by name
@@ -638,4 +1234,76 @@ mod tests {
         session.add_provider(EtwProvider::by_guid(Uuid::new_v4()));
         assert_eq!(session.providers.len(), 1);
     }
+
+    #[test]
+    fn test_default_overflow_policy_is_drop_newest() {
+        let config = SessionConfig::default();
+        assert_eq!(config.overflow_policy, OverflowPolicy::DropNewest);
+    }
+
+    #[test]
+    fn test_spill_to_file_requires_spill_path() {
+        let (_tx, rx) = bounded::<EtwEvent>(1);
+        let config = SessionConfig {
+            overflow_policy: OverflowPolicy::SpillToFile,
+            spill_path: None,
+            ..SessionConfig::default()
+        };
+        assert!(OverflowHandler::build(&config, rx).is_err());
+    }
+
+    #[test]
+    fn test_drop_newest_counts_event_lost_without_resending() {
+        let (tx, rx) = bounded::<EtwEvent>(1);
+        let overflow = OverflowHandler::build(&SessionConfig::default(), rx.clone()).unwrap();
+        let stats = Arc::new(StatsTracker::new(64, 64));
+
+        overflow.handle_overflow(EtwEvent::new(Uuid::new_v4(), 1), &tx, &stats);
+
+        assert_eq!(stats.snapshot().events_lost, 1);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_drop_oldest_makes_room_for_new_event() {
+        let (tx, rx) = bounded::<EtwEvent>(1);
+        tx.try_send(EtwEvent::new(Uuid::new_v4(), 1)).unwrap();
+        let config = SessionConfig {
+            overflow_policy: OverflowPolicy::DropOldest,
+            ..SessionConfig::default()
+        };
+        let overflow = OverflowHandler::build(&config, rx.clone()).unwrap();
+        let stats = Arc::new(StatsTracker::new(64, 64));
+
+        overflow.handle_overflow(EtwEvent::new(Uuid::new_v4(), 2), &tx, &stats);
+
+        assert_eq!(stats.snapshot().events_lost, 0);
+        assert_eq!(rx.try_recv().unwrap().event_id, 2);
+    }
+
+    #[test]
+    fn test_spill_to_file_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("pyetwkit-session-spill-{}", Uuid::new_v4()));
+        let path = dir.join("spill.bin");
+        let (tx, rx) = bounded::<EtwEvent>(1);
+        let config = SessionConfig {
+            overflow_policy: OverflowPolicy::SpillToFile,
+            spill_path: Some(path.to_string_lossy().to_string()),
+            ..SessionConfig::default()
+        };
+        let overflow = OverflowHandler::build(&config, rx.clone()).unwrap();
+        let stats = Arc::new(StatsTracker::new(64, 64));
+
+        // Channel is empty, so the overflowing event spills rather than being lost
+        overflow.handle_overflow(EtwEvent::new(Uuid::new_v4(), 3), &tx, &stats);
+        assert_eq!(stats.snapshot().events_spilled, 1);
+
+        // Once the channel has room, recover_one replays it
+        let _ = rx.try_recv();
+        overflow.recover_one(&tx, &stats);
+        assert_eq!(stats.snapshot().events_recovered, 1);
+        assert_eq!(rx.try_recv().unwrap().event_id, 3);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
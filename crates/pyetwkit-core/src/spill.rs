@@ -0,0 +1,176 @@
+//! Disk-backed overflow queue for [`crate::session::OverflowPolicy::SpillToFile`]
+//!
+//! When a session's bounded event channel is full and the configured
+//! overflow policy is `SpillToFile`, overflowed events are appended here
+//! instead of being dropped. Once the channel has room again, the session
+//! pops events back off the front and re-sends them, so nothing is lost —
+//! only delayed. The file is reclaimed (truncated back to empty) once fully
+//! drained, so a session that only occasionally bursts doesn't grow an
+//! ever-larger spill file on disk.
+
+use crate::error::{EtwError, Result};
+use crate::event::EtwEvent;
+
+use parking_lot::Mutex;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Append-only, length-prefixed spill file plus a read cursor tracking how
+/// much of it has already been popped
+pub struct SpillQueue {
+    path: PathBuf,
+    write_file: Mutex<File>,
+    read_offset: AtomicU64,
+    spilled: AtomicU64,
+    recovered: AtomicU64,
+}
+
+impl SpillQueue {
+    /// Open (or create) a spill file at `path`
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        let write_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .read(true)
+            .open(&path)?;
+
+        Ok(Self {
+            path,
+            write_file: Mutex::new(write_file),
+            read_offset: AtomicU64::new(0),
+            spilled: AtomicU64::new(0),
+            recovered: AtomicU64::new(0),
+        })
+    }
+
+    /// Append an overflowed event to the end of the spill file
+    pub fn push(&self, event: &EtwEvent) -> Result<()> {
+        let payload =
+            serde_json::to_vec(event).map_err(|e| EtwError::Internal(e.to_string()))?;
+
+        let mut file = self.write_file.lock();
+        file.write_all(&(payload.len() as u32).to_le_bytes())?;
+        file.write_all(&payload)?;
+        file.flush()?;
+        self.spilled.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Pop the oldest spilled event, if any, advancing the read cursor past
+    /// it. Reclaims the file once the cursor catches up to the write end.
+    pub fn pop_one(&self) -> Result<Option<EtwEvent>> {
+        let mut reader = File::open(&self.path)?;
+        let offset = self.read_offset.load(Ordering::Relaxed);
+        reader.seek(SeekFrom::Start(offset))?;
+
+        let mut len_buf = [0u8; 4];
+        if reader.read_exact(&mut len_buf).is_err() {
+            return Ok(None);
+        }
+        let len = u32::from_le_bytes(len_buf) as usize;
+        let mut payload = vec![0u8; len];
+        if reader.read_exact(&mut payload).is_err() {
+            return Ok(None);
+        }
+
+        let event: EtwEvent = serde_json::from_slice(&payload)
+            .map_err(|e| EtwError::DecodeError(e.to_string()))?;
+        let new_offset = offset + 4 + len as u64;
+        self.read_offset.store(new_offset, Ordering::Relaxed);
+        self.recovered.fetch_add(1, Ordering::Relaxed);
+
+        let mut writer = self.write_file.lock();
+        let write_end = writer.metadata().map(|m| m.len()).unwrap_or(new_offset);
+        if new_offset >= write_end {
+            if writer.set_len(0).is_ok() {
+                let _ = writer.seek(SeekFrom::Start(0));
+                self.read_offset.store(0, Ordering::Relaxed);
+            }
+        }
+
+        Ok(Some(event))
+    }
+
+    /// Number of events appended via [`Self::push`] over this queue's lifetime
+    pub fn spilled_count(&self) -> u64 {
+        self.spilled.load(Ordering::Relaxed)
+    }
+
+    /// Number of events popped back off via [`Self::pop_one`] over this queue's lifetime
+    pub fn recovered_count(&self) -> u64 {
+        self.recovered.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("pyetwkit-spill-test-{name}-{}.bin", Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_pop_one_empty_queue() {
+        let path = temp_path("empty");
+        let queue = SpillQueue::open(&path).unwrap();
+        assert!(queue.pop_one().unwrap().is_none());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_push_then_pop_roundtrip() {
+        let path = temp_path("roundtrip");
+        let queue = SpillQueue::open(&path).unwrap();
+
+        let event = EtwEvent::new(Uuid::new_v4(), 7);
+        queue.push(&event).unwrap();
+
+        let popped = queue.pop_one().unwrap().expect("event was pushed");
+        assert_eq!(popped.event_id, 7);
+        assert_eq!(queue.spilled_count(), 1);
+        assert_eq!(queue.recovered_count(), 1);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_fifo_order_preserved() {
+        let path = temp_path("fifo");
+        let queue = SpillQueue::open(&path).unwrap();
+
+        for id in 0..5u16 {
+            queue.push(&EtwEvent::new(Uuid::new_v4(), id)).unwrap();
+        }
+
+        for id in 0..5u16 {
+            let popped = queue.pop_one().unwrap().expect("event was pushed");
+            assert_eq!(popped.event_id, id);
+        }
+        assert!(queue.pop_one().unwrap().is_none());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_reclaims_file_once_fully_drained() {
+        let path = temp_path("reclaim");
+        let queue = SpillQueue::open(&path).unwrap();
+
+        queue.push(&EtwEvent::new(Uuid::new_v4(), 1)).unwrap();
+        queue.pop_one().unwrap();
+
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 0);
+
+        std::fs::remove_file(&path).ok();
+    }
+}
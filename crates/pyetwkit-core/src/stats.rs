@@ -1,10 +1,27 @@
 //! Session statistics and monitoring
 
+use parking_lot::Mutex;
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
+use uuid::Uuid;
+
+/// Number of one-second buckets kept for the sliding-window rate
+const WINDOW_SECONDS: usize = 60;
+/// Number of trailing buckets summed for `events_per_second_recent`
+const RECENT_WINDOW_SECONDS: u64 = 10;
+
+/// Per-provider event counts
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ProviderStats {
+    /// Number of events received from this provider
+    pub events_received: u64,
+    /// Number of events successfully processed from this provider
+    pub events_processed: u64,
+}
 
 /// Session statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +32,10 @@ pub struct SessionStats {
     pub events_processed: u64,
     /// Number of events lost due to buffer overflow
     pub events_lost: u64,
+    /// Number of events spilled to disk under `OverflowPolicy::SpillToFile`
+    pub events_spilled: u64,
+    /// Number of previously spilled events recovered back into the event channel
+    pub events_recovered: u64,
     /// Number of buffers lost
     pub buffers_lost: u64,
     /// Number of buffers read
@@ -27,8 +48,16 @@ pub struct SessionStats {
     pub start_time: i64,
     /// Duration in seconds
     pub duration_secs: f64,
-    /// Events per second
+    /// Events per second (lifetime average)
     pub events_per_second: f64,
+    /// Events per second over the last [`RECENT_WINDOW_SECONDS`] seconds
+    pub events_per_second_recent: f64,
+    /// Per-provider event counts, keyed by provider GUID string
+    pub per_provider: HashMap<String, ProviderStats>,
+    /// Rule match counts keyed by severity name (e.g. "info"/"warning"/
+    /// "error"/"critical"), as reported by a [`crate::rules::RuleSet`]
+    /// attached via [`StatsTracker::record_rule_match`]
+    pub rule_matches: HashMap<String, u64>,
 }
 
 impl Default for SessionStats {
@@ -37,6 +66,8 @@ impl Default for SessionStats {
             events_received: 0,
             events_processed: 0,
             events_lost: 0,
+            events_spilled: 0,
+            events_recovered: 0,
             buffers_lost: 0,
             buffers_read: 0,
             buffer_size_kb: 64,
@@ -44,21 +75,94 @@ impl Default for SessionStats {
             start_time: 0,
             duration_secs: 0.0,
             events_per_second: 0.0,
+            events_per_second_recent: 0.0,
+            per_provider: HashMap::new(),
+            rule_matches: HashMap::new(),
+        }
+    }
+}
+
+/// Fixed-size ring of one-second buckets used to compute a recent events/sec
+/// rate without holding a lock on the hot path.
+struct RecentRate {
+    buckets: [AtomicU64; WINDOW_SECONDS],
+    /// Second (relative to tracker start) the buckets were last advanced to
+    last_sec: AtomicU64,
+}
+
+impl RecentRate {
+    fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            last_sec: AtomicU64::new(0),
+        }
+    }
+
+    /// Clear any buckets for seconds that elapsed since the last update,
+    /// so idle periods don't leave stale counts behind.
+    fn advance_to(&self, now_sec: u64) {
+        let last = self.last_sec.load(Ordering::Relaxed);
+        if now_sec <= last {
+            return;
+        }
+        let gap = (now_sec - last).min(WINDOW_SECONDS as u64);
+        for i in 0..gap {
+            let idx = ((last + 1 + i) % WINDOW_SECONDS as u64) as usize;
+            self.buckets[idx].store(0, Ordering::Relaxed);
+        }
+        self.last_sec.store(now_sec, Ordering::Relaxed);
+    }
+
+    fn record(&self, now_sec: u64) {
+        self.advance_to(now_sec);
+        let idx = (now_sec % WINDOW_SECONDS as u64) as usize;
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Sum of the last `RECENT_WINDOW_SECONDS` buckets, divided by the
+    /// window length (or by elapsed seconds if the session is younger).
+    fn rate(&self, now_sec: u64, elapsed_secs: f64) -> f64 {
+        self.advance_to(now_sec);
+        let window = RECENT_WINDOW_SECONDS.min(WINDOW_SECONDS as u64);
+        let sum: u64 = (0..window)
+            .map(|i| {
+                let idx = (now_sec.saturating_sub(i) % WINDOW_SECONDS as u64) as usize;
+                self.buckets[idx].load(Ordering::Relaxed)
+            })
+            .sum();
+        let divisor = elapsed_secs.min(window as f64);
+        if divisor <= 0.0 {
+            0.0
+        } else {
+            sum as f64 / divisor
         }
     }
 }
 
 /// Thread-safe statistics tracker
-#[derive(Debug)]
 pub struct StatsTracker {
     events_received: AtomicU64,
     events_processed: AtomicU64,
     events_lost: AtomicU64,
+    events_spilled: AtomicU64,
+    events_recovered: AtomicU64,
     buffers_lost: AtomicU64,
     buffers_read: AtomicU64,
     start_time: Instant,
     buffer_size_kb: u32,
     buffers_allocated: u32,
+    per_provider: Mutex<HashMap<Uuid, ProviderStats>>,
+    recent_rate: RecentRate,
+    rule_matches: Mutex<HashMap<String, u64>>,
+}
+
+impl std::fmt::Debug for StatsTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatsTracker")
+            .field("events_received", &self.events_received)
+            .field("events_processed", &self.events_processed)
+            .finish()
+    }
 }
 
 impl StatsTracker {
@@ -68,22 +172,38 @@ impl StatsTracker {
             events_received: AtomicU64::new(0),
             events_processed: AtomicU64::new(0),
             events_lost: AtomicU64::new(0),
+            events_spilled: AtomicU64::new(0),
+            events_recovered: AtomicU64::new(0),
             buffers_lost: AtomicU64::new(0),
             buffers_read: AtomicU64::new(0),
             start_time: Instant::now(),
             buffer_size_kb,
             buffers_allocated,
+            per_provider: Mutex::new(HashMap::new()),
+            recent_rate: RecentRate::new(),
+            rule_matches: Mutex::new(HashMap::new()),
         }
     }
 
-    /// Increment events received counter
-    pub fn record_event_received(&self) {
+    /// Increment events received counter, attributing the event to `provider`
+    pub fn record_event_received(&self, provider: Uuid) {
         self.events_received.fetch_add(1, Ordering::Relaxed);
+        self.per_provider
+            .lock()
+            .entry(provider)
+            .or_default()
+            .events_received += 1;
     }
 
-    /// Increment events processed counter
-    pub fn record_event_processed(&self) {
+    /// Increment events processed counter, attributing the event to `provider`
+    pub fn record_event_processed(&self, provider: Uuid) {
         self.events_processed.fetch_add(1, Ordering::Relaxed);
+        self.per_provider
+            .lock()
+            .entry(provider)
+            .or_default()
+            .events_processed += 1;
+        self.recent_rate.record(self.start_time.elapsed().as_secs());
     }
 
     /// Record lost events
@@ -91,6 +211,16 @@ impl StatsTracker {
         self.events_lost.fetch_add(count, Ordering::Relaxed);
     }
 
+    /// Record an event spilled to disk under `OverflowPolicy::SpillToFile`
+    pub fn record_event_spilled(&self) {
+        self.events_spilled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a previously spilled event recovered back into the event channel
+    pub fn record_event_recovered(&self) {
+        self.events_recovered.fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Record lost buffers
     pub fn record_buffers_lost(&self, count: u64) {
         self.buffers_lost.fetch_add(count, Ordering::Relaxed);
@@ -101,6 +231,13 @@ impl StatsTracker {
         self.buffers_read.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record a rule match at the given severity name (e.g. "warning"),
+    /// as reported by a [`crate::rules::RuleSet`] attached via
+    /// [`crate::rules::RuleSet::with_stats`]
+    pub fn record_rule_match(&self, severity: &str) {
+        *self.rule_matches.lock().entry(severity.to_string()).or_insert(0) += 1;
+    }
+
     /// Get current statistics snapshot
     pub fn snapshot(&self) -> SessionStats {
         let duration = self.start_time.elapsed();
@@ -108,10 +245,19 @@ impl StatsTracker {
         let events_processed = self.events_processed.load(Ordering::Relaxed);
         let duration_secs = duration.as_secs_f64();
 
+        let per_provider = self
+            .per_provider
+            .lock()
+            .iter()
+            .map(|(guid, stats)| (guid.to_string(), *stats))
+            .collect();
+
         SessionStats {
             events_received,
             events_processed,
             events_lost: self.events_lost.load(Ordering::Relaxed),
+            events_spilled: self.events_spilled.load(Ordering::Relaxed),
+            events_recovered: self.events_recovered.load(Ordering::Relaxed),
             buffers_lost: self.buffers_lost.load(Ordering::Relaxed),
             buffers_read: self.buffers_read.load(Ordering::Relaxed),
             buffer_size_kb: self.buffer_size_kb,
@@ -126,6 +272,9 @@ impl StatsTracker {
             } else {
                 0.0
             },
+            events_per_second_recent: self.recent_rate.rate(duration.as_secs(), duration_secs),
+            per_provider,
+            rule_matches: self.rule_matches.lock().clone(),
         }
     }
 
@@ -134,8 +283,12 @@ impl StatsTracker {
         self.events_received.store(0, Ordering::Relaxed);
         self.events_processed.store(0, Ordering::Relaxed);
         self.events_lost.store(0, Ordering::Relaxed);
+        self.events_spilled.store(0, Ordering::Relaxed);
+        self.events_recovered.store(0, Ordering::Relaxed);
         self.buffers_lost.store(0, Ordering::Relaxed);
         self.buffers_read.store(0, Ordering::Relaxed);
+        self.per_provider.lock().clear();
+        self.rule_matches.lock().clear();
     }
 }
 
@@ -175,6 +328,18 @@ impl PySessionStats {
         self.inner.events_lost
     }
 
+    /// Number of events spilled to disk under `OverflowPolicy.SPILL_TO_FILE`
+    #[getter]
+    fn events_spilled(&self) -> u64 {
+        self.inner.events_spilled
+    }
+
+    /// Number of previously spilled events recovered back into the event stream
+    #[getter]
+    fn events_recovered(&self) -> u64 {
+        self.inner.events_recovered
+    }
+
     /// Number of buffers lost
     #[getter]
     fn buffers_lost(&self) -> u64 {
@@ -205,12 +370,37 @@ impl PySessionStats {
         self.inner.duration_secs
     }
 
-    /// Events per second
+    /// Events per second (lifetime average)
     #[getter]
     fn events_per_second(&self) -> f64 {
         self.inner.events_per_second
     }
 
+    /// Events per second over the last ~10 seconds
+    #[getter]
+    fn events_per_second_recent(&self) -> f64 {
+        self.inner.events_per_second_recent
+    }
+
+    /// Per-provider event counts, keyed by provider GUID string
+    #[getter]
+    fn per_provider(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let dict = pyo3::types::PyDict::new(py);
+        for (guid, stats) in &self.inner.per_provider {
+            let entry = pyo3::types::PyDict::new(py);
+            entry.set_item("events_received", stats.events_received)?;
+            entry.set_item("events_processed", stats.events_processed)?;
+            dict.set_item(guid, entry)?;
+        }
+        Ok(dict.into())
+    }
+
+    /// Rule match counts so far, keyed by severity name
+    #[getter]
+    fn rule_matches(&self) -> HashMap<String, u64> {
+        self.inner.rule_matches.clone()
+    }
+
     /// Check if any events were lost
     fn has_loss(&self) -> bool {
         self.inner.events_lost > 0 || self.inner.buffers_lost > 0
@@ -232,12 +422,17 @@ impl PySessionStats {
         dict.set_item("events_received", self.inner.events_received)?;
         dict.set_item("events_processed", self.inner.events_processed)?;
         dict.set_item("events_lost", self.inner.events_lost)?;
+        dict.set_item("events_spilled", self.inner.events_spilled)?;
+        dict.set_item("events_recovered", self.inner.events_recovered)?;
         dict.set_item("buffers_lost", self.inner.buffers_lost)?;
         dict.set_item("buffers_read", self.inner.buffers_read)?;
         dict.set_item("buffer_size_kb", self.inner.buffer_size_kb)?;
         dict.set_item("buffers_allocated", self.inner.buffers_allocated)?;
         dict.set_item("duration_secs", self.inner.duration_secs)?;
         dict.set_item("events_per_second", self.inner.events_per_second)?;
+        dict.set_item("events_per_second_recent", self.inner.events_per_second_recent)?;
+        dict.set_item("per_provider", self.per_provider(py)?)?;
+        dict.set_item("rule_matches", self.rule_matches())?;
         Ok(dict.into())
     }
 
@@ -262,10 +457,11 @@ mod tests {
     #[test]
     fn test_stats_tracker() {
         let tracker = StatsTracker::new(64, 64);
+        let provider = Uuid::new_v4();
 
-        tracker.record_event_received();
-        tracker.record_event_received();
-        tracker.record_event_processed();
+        tracker.record_event_received(provider);
+        tracker.record_event_received(provider);
+        tracker.record_event_processed(provider);
         tracker.record_events_lost(5);
 
         let stats = tracker.snapshot();
@@ -278,13 +474,65 @@ mod tests {
     fn test_stats_reset() {
         let tracker = StatsTracker::new(64, 64);
 
-        tracker.record_event_received();
+        tracker.record_event_received(Uuid::new_v4());
         tracker.record_events_lost(10);
         tracker.reset();
 
         let stats = tracker.snapshot();
         assert_eq!(stats.events_received, 0);
         assert_eq!(stats.events_lost, 0);
+        assert!(stats.per_provider.is_empty());
+    }
+
+    #[test]
+    fn test_per_provider_counters() {
+        let tracker = StatsTracker::new(64, 64);
+        let provider_a = Uuid::new_v4();
+        let provider_b = Uuid::new_v4();
+
+        tracker.record_event_received(provider_a);
+        tracker.record_event_processed(provider_a);
+        tracker.record_event_received(provider_b);
+
+        let stats = tracker.snapshot();
+        assert_eq!(stats.per_provider.len(), 2);
+        assert_eq!(
+            stats.per_provider[&provider_a.to_string()].events_processed,
+            1
+        );
+        assert_eq!(
+            stats.per_provider[&provider_b.to_string()].events_received,
+            1
+        );
+    }
+
+    #[test]
+    fn test_recent_rate_reflects_processed_events() {
+        let tracker = StatsTracker::new(64, 64);
+        let provider = Uuid::new_v4();
+
+        for _ in 0..5 {
+            tracker.record_event_processed(provider);
+        }
+
+        let stats = tracker.snapshot();
+        assert!(stats.events_per_second_recent > 0.0);
+    }
+
+    #[test]
+    fn test_rule_match_counters() {
+        let tracker = StatsTracker::new(64, 64);
+
+        tracker.record_rule_match("warning");
+        tracker.record_rule_match("warning");
+        tracker.record_rule_match("critical");
+
+        let stats = tracker.snapshot();
+        assert_eq!(stats.rule_matches["warning"], 2);
+        assert_eq!(stats.rule_matches["critical"], 1);
+
+        tracker.reset();
+        assert!(tracker.snapshot().rule_matches.is_empty());
     }
 
     #[test]
@@ -0,0 +1,236 @@
+//! TraceLogging self-describing metadata decoder
+//!
+//! Manifest-free TraceLogging events carry their schema inline in an
+//! `EVENT_HEADER_EXT_TYPE_TRACE_MESSAGE` metadata blob rather than in a
+//! registered manifest, so `TdhGetEventInformation` can't resolve them.
+//! [`decode_tlg_metadata`] parses that blob directly into an [`EventSchema`]
+//! with `schema_type` set to [`SchemaType::TraceLogging`].
+
+use crate::error::{EtwError, Result};
+use crate::schema::{DecodingSource, EventSchema, PropertyInfo, PropertyType, SchemaType};
+
+/// Low 5 bits of a TraceLogging field's packed in-type byte select the
+/// primitive wire type (`TlgIn*` in `TraceLoggingProvider.h`)
+mod tlg_in_type {
+    pub const NULL: u8 = 0;
+    pub const UNICODE_STRING: u8 = 1;
+    pub const ANSI_STRING: u8 = 2;
+    pub const INT8: u8 = 3;
+    pub const UINT8: u8 = 4;
+    pub const INT16: u8 = 5;
+    pub const UINT16: u8 = 6;
+    pub const INT32: u8 = 7;
+    pub const UINT32: u8 = 8;
+    pub const INT64: u8 = 9;
+    pub const UINT64: u8 = 10;
+    pub const FLOAT: u8 = 11;
+    pub const DOUBLE: u8 = 12;
+    pub const BOOL32: u8 = 13;
+    pub const BINARY: u8 = 14;
+    pub const GUID: u8 = 15;
+    pub const POINTER: u8 = 16;
+    pub const FILETIME: u8 = 17;
+    pub const SYSTEMTIME: u8 = 18;
+    pub const SID: u8 = 19;
+    pub const HEXINT32: u8 = 20;
+    pub const HEXINT64: u8 = 21;
+}
+
+const TLG_IN_TYPE_MASK: u8 = 0x1F;
+/// High bit: an out-type byte follows the in-type byte
+const TLG_CHAIN_FLAG: u8 = 0x80;
+/// Next-highest bit: a fixed element count (`u16`) follows
+const TLG_ARRAY_FLAG: u8 = 0x40;
+/// Third-highest bit: a variable element count is supplied at event-write
+/// time rather than in the metadata blob
+const TLG_VARIABLE_COUNT_FLAG: u8 = 0x20;
+
+/// Bounds-checked cursor over a TraceLogging metadata blob
+struct Blob<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Blob<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn peek(&self) -> Result<u8> {
+        self.bytes.get(self.pos).copied().ok_or_else(|| {
+            EtwError::DecodeError("unexpected end of TraceLogging metadata".to_string())
+        })
+    }
+
+    fn advance_u8(&mut self) -> Result<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn advance_u16(&mut self) -> Result<u16> {
+        let lo = self.advance_u8()?;
+        let hi = self.advance_u8()?;
+        Ok(u16::from_le_bytes([lo, hi]))
+    }
+
+    /// Read a NUL-terminated, lossily-decoded UTF-8 string
+    fn advance_cstr(&mut self) -> Result<String> {
+        let start = self.pos;
+        loop {
+            if self.advance_u8()? == 0 {
+                break;
+            }
+        }
+        Ok(String::from_utf8_lossy(&self.bytes[start..self.pos - 1]).into_owned())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+}
+
+/// Parse a TraceLogging self-describing metadata blob (the payload of an
+/// `EVENT_HEADER_EXT_TYPE_TRACE_MESSAGE` header extension) into an
+/// [`EventSchema`]. `provider_id`/`event_id`/`version` come from the event
+/// record itself, since the metadata blob only carries the event name and
+/// field descriptors.
+pub fn decode_tlg_metadata(
+    provider_id: &str,
+    event_id: u16,
+    version: u8,
+    blob: &[u8],
+) -> Result<EventSchema> {
+    let mut cursor = Blob::new(blob);
+    let event_name = cursor.advance_cstr()?;
+
+    let mut properties = Vec::new();
+    while !cursor.is_empty() {
+        let name = cursor.advance_cstr()?;
+        let packed_in_type = cursor.advance_u8()?;
+
+        if packed_in_type & TLG_CHAIN_FLAG != 0 {
+            // Out-type doesn't change the wire layout we decode into
+            // PropertyType, so just consume it.
+            cursor.advance_u8()?;
+        }
+        let is_array = packed_in_type & (TLG_ARRAY_FLAG | TLG_VARIABLE_COUNT_FLAG) != 0;
+        if packed_in_type & TLG_ARRAY_FLAG != 0 {
+            cursor.advance_u16()?;
+        }
+
+        let in_type = packed_in_type & TLG_IN_TYPE_MASK;
+        properties.push(PropertyInfo {
+            name,
+            property_type: tlg_in_type_to_property_type(in_type),
+            length: None,
+            is_array,
+            value_map: None,
+            is_bitmap: false,
+        });
+    }
+
+    Ok(EventSchema {
+        provider_id: provider_id.to_string(),
+        event_id,
+        version,
+        event_name: if event_name.is_empty() {
+            None
+        } else {
+            Some(event_name)
+        },
+        properties,
+        schema_type: SchemaType::TraceLogging,
+        decoding_source: DecodingSource::Tlg,
+    })
+}
+
+fn tlg_in_type_to_property_type(in_type: u8) -> PropertyType {
+    match in_type {
+        tlg_in_type::NULL => PropertyType::Null,
+        tlg_in_type::UNICODE_STRING | tlg_in_type::ANSI_STRING => PropertyType::String,
+        tlg_in_type::INT8 => PropertyType::Int8,
+        tlg_in_type::UINT8 => PropertyType::UInt8,
+        tlg_in_type::INT16 => PropertyType::Int16,
+        tlg_in_type::UINT16 => PropertyType::UInt16,
+        tlg_in_type::INT32 => PropertyType::Int32,
+        tlg_in_type::UINT32 => PropertyType::UInt32,
+        tlg_in_type::INT64 => PropertyType::Int64,
+        tlg_in_type::UINT64 => PropertyType::UInt64,
+        tlg_in_type::FLOAT => PropertyType::Float,
+        tlg_in_type::DOUBLE => PropertyType::Double,
+        tlg_in_type::BOOL32 => PropertyType::Boolean,
+        tlg_in_type::BINARY => PropertyType::Binary,
+        tlg_in_type::GUID => PropertyType::Guid,
+        tlg_in_type::POINTER => PropertyType::Pointer,
+        tlg_in_type::FILETIME => PropertyType::FileTime,
+        tlg_in_type::SYSTEMTIME => PropertyType::SystemTime,
+        tlg_in_type::SID => PropertyType::Sid,
+        tlg_in_type::HEXINT32 => PropertyType::HexInt32,
+        tlg_in_type::HEXINT64 => PropertyType::HexInt64,
+        _ => PropertyType::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_field(blob: &mut Vec<u8>, name: &str, in_type: u8) {
+        blob.extend_from_slice(name.as_bytes());
+        blob.push(0);
+        blob.push(in_type);
+    }
+
+    #[test]
+    fn test_decode_tlg_metadata() {
+        let mut blob = Vec::new();
+        blob.extend_from_slice(b"MyEvent\0");
+        push_field(&mut blob, "ProcessId", tlg_in_type::UINT32);
+        push_field(&mut blob, "Message", tlg_in_type::UNICODE_STRING);
+
+        let schema = decode_tlg_metadata("test-provider", 1, 0, &blob).unwrap();
+        assert_eq!(schema.event_name.as_deref(), Some("MyEvent"));
+        assert_eq!(schema.schema_type, SchemaType::TraceLogging);
+        assert_eq!(schema.properties.len(), 2);
+        assert_eq!(schema.properties[0].property_type, PropertyType::UInt32);
+        assert_eq!(schema.properties[1].property_type, PropertyType::String);
+        assert!(!schema.properties[0].is_array);
+    }
+
+    #[test]
+    fn test_decode_tlg_metadata_array_field() {
+        let mut blob = Vec::new();
+        blob.extend_from_slice(b"ArrayEvent\0");
+        blob.extend_from_slice(b"Items\0");
+        blob.push(tlg_in_type::UINT32 | TLG_ARRAY_FLAG);
+        blob.extend_from_slice(&3u16.to_le_bytes());
+
+        let schema = decode_tlg_metadata("test-provider", 2, 0, &blob).unwrap();
+        assert_eq!(schema.properties.len(), 1);
+        assert!(schema.properties[0].is_array);
+    }
+
+    #[test]
+    fn test_decode_tlg_metadata_chained_out_type() {
+        let mut blob = Vec::new();
+        blob.extend_from_slice(b"Event\0");
+        blob.extend_from_slice(b"Code\0");
+        blob.push(tlg_in_type::UINT32 | TLG_CHAIN_FLAG);
+        blob.push(0x05); // arbitrary out-type byte, consumed but not interpreted
+
+        let schema = decode_tlg_metadata("test-provider", 3, 0, &blob).unwrap();
+        assert_eq!(schema.properties[0].property_type, PropertyType::UInt32);
+    }
+
+    #[test]
+    fn test_decode_tlg_metadata_truncated_errors() {
+        let blob = b"Name\0Field".to_vec();
+        assert!(decode_tlg_metadata("test-provider", 1, 0, &blob).is_err());
+    }
+
+    #[test]
+    fn test_unknown_in_type_falls_back_to_unknown() {
+        assert_eq!(tlg_in_type_to_property_type(0x1F), PropertyType::Unknown);
+    }
+}